@@ -0,0 +1,180 @@
+use std::cmp::Ordering;
+
+use cipher::Cipher;
+use fitness::Fitness;
+use freq::{letter_counts, LetterFreq};
+use modular::{modb_inv_n, modb_n};
+use text::int_to_str_lower;
+use wordlist::Wordlist;
+
+pub fn affine_single(c: u8, a: u8, b: u8, m: usize) -> u8 {
+    //affine encode a single int
+    modb_n((c as isize * a as isize) + (b as isize), m) as u8
+}
+
+pub fn deaffine_single(c: u8, a1: u8, b: u8, m: usize) -> u8 {
+    //affine decode a single int
+    modb_n((c as isize - b as isize) * a1 as isize, m) as u8
+}
+
+pub fn encode_affine(c: &[u8], a: u8, b: u8, m: usize) -> Vec<u8> {
+    //affine encode set of ints
+    c.iter().map(|&x| affine_single(x, a, b, m)).collect()
+}
+
+pub fn decode_affine(c: &[u8], a1: u8, b: u8, m: usize) -> Vec<u8> {
+    //decode affine for set of ints
+    c.iter().map(|&x| deaffine_single(x, a1, b, m)).collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct Affine {
+    //affine cipher (a*x + b) mod m, a1 is the inverse of a
+    pub a: u8,
+    pub b: u8,
+    pub a1: u8,
+    pub m: usize,
+}
+
+impl Affine {
+    pub fn new(a: u8, b: u8) -> Option<Affine> {
+        Affine::with_modulus(a, b, 26)
+    }
+
+    pub fn with_modulus(a: u8, b: u8, m: usize) -> Option<Affine> {
+        //a must have an inverse mod m for the cipher to be decodable (there's
+        //nothing mod 0)
+        if m == 0 {
+            return None;
+        }
+        let a1 = modb_inv_n(a as isize, m);
+        if a1 < 0 {
+            return None;
+        }
+        Some(Affine {
+            a: modb_n(a as isize, m) as u8,
+            b: modb_n(b as isize, m) as u8,
+            a1: a1 as u8,
+            m,
+        })
+    }
+}
+
+impl Cipher for Affine {
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        encode_affine(plain, self.a, self.b, self.m)
+    }
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        decode_affine(cipher, self.a1, self.b, self.m)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Shift {
+    //shift (ceaser) cipher, just an affine cipher with a=1
+    pub k: u8,
+    pub m: usize,
+}
+
+impl Shift {
+    pub fn new(k: u8) -> Shift {
+        Shift { k: k % 26, m: 26 }
+    }
+
+    pub fn with_modulus(k: u8, m: usize) -> Option<Shift> {
+        //any shift works, as long as there's an alphabet to shift around
+        if m == 0 {
+            return None;
+        }
+        Some(Shift {
+            k: modb_n(k as isize, m) as u8,
+            m,
+        })
+    }
+}
+
+impl Cipher for Shift {
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        encode_affine(plain, 1, self.k, self.m)
+    }
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        decode_affine(cipher, 1, self.k, self.m)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AffineCandidate {
+    //a possible key along with how english it's decryption looks, x2 is from
+    //LetterFreq::score (lower is better), words is the number of letters
+    //covered by dictionary words (higher is better)
+    pub key: Affine,
+    pub x2: f64,
+    pub words: usize,
+}
+
+pub fn crack_affine(
+    cipher: &[u8],
+    top: usize,
+    crib: Option<&[u8]>,
+    wordlist: Option<&Wordlist>,
+) -> Vec<AffineCandidate> {
+    //try all 312 valid keys, dropping any without the crib. Candidates are
+    //ranked by x2, then if a wordlist is given the best few are re-ranked by
+    //how much of the text is made of dictionary words.
+    let lf = LetterFreq::new();
+    let mut candidates: Vec<AffineCandidate> = crib_keys(cipher, crib)
+        .into_iter()
+        .map(|(key, plain)| {
+            let x2 = lf.score(&letter_counts(&plain));
+            AffineCandidate { key, x2, words: 0 }
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.x2.partial_cmp(&b.x2).unwrap_or(Ordering::Equal));
+    if let Some(wordlist) = wordlist {
+        candidates.truncate(top.max(10));
+        for c in candidates.iter_mut() {
+            c.words = wordlist.coverage(&int_to_str_lower(&c.key.decrypt(cipher)), 3, 10);
+        }
+        candidates.sort_by(|a, b| {
+            b.words
+                .cmp(&a.words)
+                .then(a.x2.partial_cmp(&b.x2).unwrap_or(Ordering::Equal))
+        });
+    }
+    candidates.truncate(top);
+    candidates
+}
+
+pub fn crack_affine_with(
+    cipher: &[u8],
+    top: usize,
+    crib: Option<&[u8]>,
+    fitness: &dyn Fitness,
+) -> Vec<(Affine, f64)> {
+    //same search as crack_affine but ranked by any fitness (higher is better),
+    //eg an NgramModel which does much better on short texts than x2
+    let mut candidates: Vec<(Affine, f64)> = crib_keys(cipher, crib)
+        .into_iter()
+        .map(|(key, plain)| (key, fitness.fitness(&plain)))
+        .collect();
+    candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    candidates.truncate(top);
+    candidates
+}
+
+fn crib_keys(cipher: &[u8], crib: Option<&[u8]>) -> Vec<(Affine, Vec<u8>)> {
+    //all 312 valid keys (12 a's with inverses, 26 b's) with their decryptions,
+    //keys where the crib doesn't show up in the decryption are thrown out
+    (1..26)
+        .flat_map(|a| (0..26).map(move |b| (a, b)))
+        .filter_map(|(a, b)| Affine::new(a, b))
+        .map(|key| {
+            let plain = key.decrypt(cipher);
+            (key, plain)
+        })
+        .filter(|(_, plain)| match crib {
+            Some(crib) if !crib.is_empty() => plain.windows(crib.len()).any(|w| w == crib),
+            _ => true,
+        })
+        .collect()
+}
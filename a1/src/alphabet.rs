@@ -0,0 +1,89 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alphabet {
+    //ordered set of characters, each character's int is it's position and
+    //the number of characters is the modulus the ciphers work in
+    chars: Vec<char>,
+}
+
+impl Alphabet {
+    pub fn new(chars: &str) -> Option<Alphabet> {
+        //custom alphabet (in the order given), must not be empty or have any
+        //character twice
+        let chars: Vec<char> = chars.chars().collect();
+        if chars.is_empty() || chars.len() > 256 {
+            return None;
+        }
+        for (i, c) in chars.iter().enumerate() {
+            if chars[..i].contains(c) {
+                return None;
+            }
+        }
+        Some(Alphabet { chars })
+    }
+
+    pub fn latin() -> Alphabet {
+        //A-Z mod26
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+    }
+
+    pub fn latin_punct() -> Alphabet {
+        //A-Z plus space, full stop and comma mod29
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ .,").unwrap()
+    }
+
+    pub fn ascii() -> Alphabet {
+        //printable ascii (space through ~) mod95
+        Alphabet {
+            chars: (32u8..127).map(|c| c as char).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        //size of the alphabet, also the modulus
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn index(&self, c: char) -> Option<u8> {
+        self.chars.iter().position(|&x| x == c).map(|i| i as u8)
+    }
+
+    pub fn lookup(&self, c: char) -> Option<(u8, bool)> {
+        //find c, or if the alphabet doesn't have it then it's upper case
+        //version, the bool is if it was lower case (and had to be changed).
+        //Only when that's one character, ß upper cases to SS which is left
+        //for folding
+        if let Some(i) = self.index(c) {
+            return Some((i, false));
+        }
+        let mut upper = c.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(u), None) if u != c => self.index(u).map(|i| (i, true)),
+            _ => None,
+        }
+    }
+
+    pub fn char_at(&self, i: u8) -> char {
+        self.chars[i as usize]
+    }
+
+    pub fn to_ints(&self, s: &str) -> Vec<u8> {
+        //convert a string to ints, characters not in the alphabet are dropped
+        s.chars()
+            .filter_map(|c| self.lookup(c).map(|(i, _)| i))
+            .collect()
+    }
+
+    pub fn to_str(&self, v: &[u8]) -> String {
+        v.iter().map(|&i| self.char_at(i)).collect()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Alphabet {
+        Alphabet::latin()
+    }
+}
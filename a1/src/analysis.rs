@@ -0,0 +1,545 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use cipher::Cipher;
+use fitness::{Fitness, NgramModel};
+use freq::{index_of_coincidence, letter_counts, LetterFreq, Scorer};
+use text::{clean_upper, from_int_lower, int_to_str_lower, str_upper_to_int};
+use vigenere::Vigenere;
+
+//index of coincidence of english text, and of random text (1/26)
+pub const KP: f64 = 0.0667;
+pub const KR: f64 = 1f64 / 26f64;
+
+#[derive(Clone, Debug)]
+pub struct KasiskiReport {
+    //spacings between repeated trigrams, how many of them are divisible by
+    //each keylength (min through max, inclusive) and the normalized ranking
+    //of keylengths made from those votes
+    pub min_keylength: usize,
+    pub max_keylength: usize,
+    pub distances: Vec<usize>,
+    pub votes: Vec<(usize, usize)>,
+    pub scores: Vec<(usize, f64)>,
+    pub best: usize,
+}
+
+impl KasiskiReport {
+    pub fn histogram(&self) -> Vec<(usize, usize)> {
+        //number of times each distance was seen, sorted by distance
+        let mut h: HashMap<usize, usize> = HashMap::new();
+        for &d in self.distances.iter() {
+            *h.entry(d).or_insert(0) += 1;
+        }
+        let mut h: Vec<(usize, usize)> = h.into_iter().collect();
+        h.sort();
+        h
+    }
+}
+
+pub fn kasiski(cipher: &str, min_keylength: usize, max_keylength: usize) -> KasiskiReport {
+    let cipher = &clean_upper(cipher);
+    let min_keylength = min_keylength.max(1);
+    let mut sets: HashMap<&str, Vec<usize>> = HashMap::new();
+    for i in 0..(cipher.len() + 1).saturating_sub(3) {
+        sets.entry(&cipher[i..i + 3]).or_default().push(i);
+    }
+    //only trigrams which appear more than once, changed to the distance
+    //between each duplicate and the next
+    let mut distances: Vec<usize> = sets
+        .values()
+        .filter(|p| p.len() > 1)
+        .flat_map(|p| p.windows(2).map(|w| w[1] - w[0]).collect::<Vec<usize>>())
+        .collect();
+    distances.sort();
+    //every distance votes for the keylengths dividing it, a repeat caused by
+    //the key lining up is a multiple of the keylength so the right keylength
+    //(and it's factors) pick up most of the votes. Every length from 2 is
+    //counted, as the factors are needed below
+    let mut all: Vec<(usize, usize)> = (2..=max_keylength).map(|k| (k, 0)).collect();
+    for &d in distances.iter() {
+        for v in all.iter_mut().filter(|v| d.is_multiple_of(v.0)) {
+            v.1 += 1;
+        }
+    }
+    let count = |k: usize| match k {
+        1 => distances.len(),
+        _ => all[k - 2].1,
+    };
+    let votes: Vec<(usize, usize)> = (min_keylength..=max_keylength)
+        .map(|k| (k, count(k)))
+        .collect();
+    //a single distance dividing by k is often chance, so k is scored on the
+    //pairs of distances which both divide by it (v*(v-1)/2 from the votes,
+    //rather than comparing every pair). Two random distances both divide by
+    //k 1/k^2 of the time, so scale by k^2 to stop the small factors of the
+    //keylength winning. That leaves multiples of the keylength tied with it
+    //(m times the keylength gets about 1/m^2 of it's pairs), so each k is
+    //also scaled by the smallest share it kept of the pairs of any of it's
+    //factors, squared. The keylength keeps most of it's factors' pairs, a
+    //multiple of it doesn't, so the shortest length which explains the
+    //repeats wins. Then normalize so the scores add to 1. Ties go to the
+    //shorter keylength
+    let pairs = |v: usize| (v * v.saturating_sub(1) / 2) as f64;
+    let raw: Vec<(usize, f64)> = votes
+        .iter()
+        .map(|&(k, v)| {
+            let kept = (2..k)
+                .filter(|&d| k.is_multiple_of(d) && pairs(count(d)) > 0f64)
+                .map(|d| pairs(v) / pairs(count(d)))
+                .fold(1f64, f64::min);
+            let lift = pairs(v) / pairs(distances.len()).max(1f64) * (k * k) as f64;
+            (k, lift * kept * kept)
+        })
+        .collect();
+    let total: f64 = raw.iter().map(|&(_, r)| r).sum();
+    let mut scores: Vec<(usize, f64)> = raw
+        .into_iter()
+        .map(|(k, r)| (k, if total > 0f64 { r / total } else { 0f64 }))
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    let best = scores
+        .first()
+        .map_or(0, |&(k, s)| if s > 0f64 { k } else { 0 });
+    KasiskiReport {
+        min_keylength,
+        max_keylength,
+        distances,
+        votes,
+        scores,
+        best,
+    }
+}
+
+impl fmt::Display for KasiskiReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Kasiski----")?;
+        writeln!(f, "Finding every trigram (length 3 match) of ciphertext...")?;
+        writeln!(f, "Reducing to only those who appear more than once...")?;
+        writeln!(
+            f,
+            "Changing set to contain distance between duplicates, instead of positions..."
+        )?;
+        writeln!(f, "Found {} distances...", self.distances.len())?;
+        writeln!(
+            f,
+            "Each distance votes for suspected keylengths {} through {} that divide it",
+            self.min_keylength, self.max_keylength
+        )?;
+        writeln!(
+            f,
+            "Each keylength is scored on the pairs of distances it divides, scaled by keylength^2 (random pairs share a factor k 1/k^2 of the time), then by the smallest share kept of any factor's pairs squared (so multiples of the keylength lose out), and normalized to add to 1"
+        )?;
+        for &(m, v) in self.votes.iter() {
+            writeln!(f, "Keylength of {} divided {} distances", m, v)?;
+        }
+        writeln!(f, "Ranking;")?;
+        for &(m, s) in self.scores.iter() {
+            writeln!(f, "Keylength of {} scored {:.3}", m, s)?;
+        }
+        writeln!(f, "Final best keylength; {}", self.best)?;
+        writeln!(f, "----Kasiski DONE----")
+    }
+}
+
+pub fn column_ics(cipherv: &[u8], k: usize) -> Vec<f64> {
+    //index of coincidence of each column when the text is split into k columns
+    //(none when k is 0)
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut cols: Vec<Vec<u8>> = vec![Vec::new(); k];
+    for (i, &c) in cipherv.iter().enumerate() {
+        cols[i % k].push(c);
+    }
+    cols.iter().map(|c| index_of_coincidence(c)).collect()
+}
+
+fn avg(v: &[f64]) -> f64 {
+    v.iter().sum::<f64>() / v.len() as f64
+}
+
+#[derive(Clone, Debug)]
+pub struct Freidman1Report {
+    //column IC's (φ) for each keylength tried, best is the keylength with
+    //the average φ closest to english
+    pub columns: Vec<(usize, Vec<f64>)>,
+    pub best: usize,
+}
+
+impl Freidman1Report {
+    pub fn averages(&self) -> Vec<(usize, f64)> {
+        self.columns.iter().map(|(k, k0)| (*k, avg(k0))).collect()
+    }
+}
+
+pub fn freidman_1(cipher: &str, min_keylength: usize, max_keylength: usize) -> Freidman1Report {
+    //every keylength from min through max, inclusive
+    let cipherv = str_upper_to_int(cipher);
+    let columns: Vec<(usize, Vec<f64>)> = (min_keylength.max(1)..=max_keylength)
+        .map(|k| (k, column_ics(&cipherv, k)))
+        .collect();
+    let mut best = 0usize;
+    let mut best_value = usize::MAX;
+    for (k, k0) in columns.iter() {
+        let avg = avg(k0);
+        if (avg * 10000f64 - 667f64).abs() < best_value as f64 {
+            best_value = (avg * 10000f64 - 667f64).abs().round() as usize;
+            best = *k;
+        }
+    }
+    Freidman1Report { columns, best }
+}
+
+impl fmt::Display for Freidman1Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Freidman1----")?;
+        for (k, k0) in self.columns.iter() {
+            write!(f, "For keylength {} φ = Avg({:.4}) ", k, avg(k0))?;
+            for x in k0.iter() {
+                write!(f, "{:.3}, ", x)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Closest keylength to φ = 0.0667; {}", self.best)?;
+        writeln!(f, "----Freidman1 DONE----")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Freidman2Report {
+    //frequency distribution and IC (k0) of the whole cipher text, with the
+    //keysize estimate (kp-kr)/(k0-kr)
+    pub freq: Vec<usize>,
+    pub k0: f64,
+    pub estimate: f64,
+}
+
+impl Freidman2Report {
+    pub fn best(&self) -> usize {
+        self.estimate.round() as usize
+    }
+}
+
+pub fn freidman_2(cipher: &str) -> Freidman2Report {
+    let cipherv = str_upper_to_int(cipher);
+    let k0 = index_of_coincidence(&cipherv);
+    Freidman2Report {
+        freq: letter_counts(&cipherv),
+        k0,
+        estimate: (KP - KR) / (k0 - KR),
+    }
+}
+
+impl fmt::Display for Freidman2Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Freidman2----")?;
+        writeln!(f, "Find k0...")?;
+        writeln!(
+            f,
+            "Frequency distribution (where first is occurences of a, second of b...);\n{:?}",
+            self.freq
+        )?;
+        writeln!(f, "k0={:.4}, kp={:.4}, kr={:.4}", self.k0, KP, KR)?;
+        writeln!(f, "Final keysize guess (as float): {:.4}", self.estimate)?;
+        writeln!(f, "----Freidman2 DONE----")
+    }
+}
+
+pub fn column_dists(cipherv: &[u8], keysize: usize) -> Vec<Vec<usize>> {
+    //frequency distribution of each of the keysize columns (none for 0)
+    if keysize == 0 {
+        return Vec::new();
+    }
+    let mut freq: Vec<Vec<usize>> = vec![vec![0usize; 26]; keysize];
+    for (i, &c) in cipherv.iter().enumerate() {
+        freq[i % keysize][c as usize] += 1;
+    }
+    freq
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyMethod {
+    //each column's shift found on it's own, by comparing it to the language
+    Independent(Scorer),
+    //columns lined up with each other by mutual index of coincidence, then
+    //one offset for the whole key found by comparing to the language
+    MutualIc,
+}
+
+#[derive(Clone, Debug)]
+pub struct VigSolveReport {
+    //frequency distribution of each column, the key found from them (by the
+    //method used) and the resulting plaintext
+    pub columns: Vec<Vec<usize>>,
+    pub method: KeyMethod,
+    pub key: Vec<u8>,
+    pub plain: Vec<u8>,
+}
+
+pub fn solve_vig(cipher: &str, keysize: usize) -> VigSolveReport {
+    solve_vig_with(cipher, keysize, &LetterFreq::new())
+}
+
+pub fn solve_vig_with(cipher: &str, keysize: usize, lf: &LetterFreq) -> VigSolveReport {
+    //solve against the letter frequencies of any language
+    let cipherv = str_upper_to_int(cipher);
+    let columns = column_dists(&cipherv, keysize);
+    //the best shift maps the cipher text back to plaintext, so the key is
+    //the shift in the opposite direction
+    let key: Vec<u8> = columns
+        .iter()
+        .map(|set| (26 - lf.get_best_x2_shift(set.clone()) % 26) % 26)
+        .collect();
+    //a keysize of 0 has no columns, so gives an empty key and plaintext
+    let plain = Vigenere::new(key.clone()).map_or_else(Vec::new, |v| v.decrypt(&cipherv));
+    VigSolveReport {
+        columns,
+        method: KeyMethod::Independent(lf.scorer),
+        key,
+        plain,
+    }
+}
+
+impl fmt::Display for VigSolveReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Finding Key----")?;
+        writeln!(f, "Checking for keysize = {}", self.key.len())?;
+        writeln!(
+            f,
+            "Generating {} sets of frequency distributions (keylength)",
+            self.key.len()
+        )?;
+        match self.method {
+            KeyMethod::Independent(Scorer::ChiSquared) => writeln!(
+                f,
+                "Using X^2 method for finding best single keys; (actual-expected)^2/expected per letter, summed. (lowest value is closest to expected distribution)"
+            )?,
+            KeyMethod::Independent(Scorer::GTest) => writeln!(
+                f,
+                "Using G-test for finding best single keys; 2*actual*ln(actual/expected) per letter, summed. (lowest value is closest to expected distribution)"
+            )?,
+            KeyMethod::Independent(Scorer::Cosine) => writeln!(
+                f,
+                "Using cosine similarity for finding best single keys; angle between actual and expected distributions. (highest similarity is closest to expected distribution)"
+            )?,
+            KeyMethod::MutualIc => {
+                writeln!(
+                    f,
+                    "Using mutual index of coincidence to find the shift of each set relative to the first (highest is the best match)"
+                )?;
+                writeln!(
+                    f,
+                    "Then trying all 26 offsets for the whole key, keeping the decryption with the lowest X^2"
+                )?;
+            }
+        }
+        let verb = match self.method {
+            KeyMethod::Independent(_) => "Finding best single key",
+            KeyMethod::MutualIc => "Shifted key",
+        };
+        for (i, &x) in self.key.iter().enumerate() {
+            writeln!(f, "{} for set {}; {}", verb, i + 1, from_int_lower(x))?;
+        }
+        writeln!(f, "Expected key; {}", int_to_str_lower(&self.key))
+    }
+}
+
+pub fn mutual_ic(a: &[usize], b: &[usize], s: usize) -> f64 {
+    //chance a letter from a and a letter from b shifted back by s are the
+    //same, close to KP when b is a shifted s further than a, otherwise near KR
+    let (na, nb): (usize, usize) = (a.iter().sum(), b.iter().sum());
+    if na == 0 || nb == 0 {
+        return 0f64;
+    }
+    let l = a.len();
+    let same = (0..l).fold(0, |x, c| x + a[c] * b[(c + s) % l]);
+    same as f64 / (na * nb) as f64
+}
+
+pub fn solve_vig_mic(cipher: &str, keysize: usize) -> VigSolveReport {
+    solve_vig_mic_with(cipher, keysize, &LetterFreq::new())
+}
+
+pub fn solve_vig_mic_with(cipher: &str, keysize: usize, lf: &LetterFreq) -> VigSolveReport {
+    //rather than matching each column to the language, line every column up
+    //against all of the others (the sum of the mutual ic with each of them,
+    //using the shifts the columns give on their own). This doesn't rely on
+    //the letter frequencies matching the language so well, but a column which
+    //is wrong on it's own gets pulled back in line by the rest. Only done
+    //once, as repeating it lets a few wrong columns drag the others away.
+    //This only finds the key up to a constant, which is then found using the
+    //whole text. A keysize of 0 gives an empty key and plaintext
+    let cipherv = str_upper_to_int(cipher);
+    let columns = column_dists(&cipherv, keysize);
+    if keysize == 0 {
+        return VigSolveReport {
+            columns,
+            method: KeyMethod::MutualIc,
+            key: Vec::new(),
+            plain: Vec::new(),
+        };
+    }
+    let start = solve_vig_with(cipher, keysize, lf).key;
+    let shifts: Vec<u8> = (0..keysize)
+        .map(|j| {
+            let total = |s: u8| {
+                (0..keysize).filter(|&i| i != j).fold(0f64, |x, i| {
+                    x + mutual_ic(
+                        &columns[i],
+                        &columns[j],
+                        ((s + 26 - start[i]) % 26) as usize,
+                    )
+                })
+            };
+            (0..26u8)
+                .max_by(|&s1, &s2| total(s1).total_cmp(&total(s2)).then(s2.cmp(&s1)))
+                .unwrap_or(0)
+        })
+        .collect();
+    let rel: Vec<u8> = shifts.iter().map(|&s| (s + 26 - shifts[0]) % 26).collect();
+    let key: Vec<u8> = (0..26u8)
+        .map(|g| rel.iter().map(|&r| (r + g) % 26).collect::<Vec<u8>>())
+        .min_by(|k1, k2| {
+            let score = |k: &[u8]| {
+                lf.score(&letter_counts(
+                    &Vigenere::new(k.to_vec())
+                        .expect("keysize is at least 1")
+                        .decrypt(&cipherv),
+                ))
+            };
+            score(k1).total_cmp(&score(k2))
+        })
+        .unwrap_or_default();
+    let plain = Vigenere::new(key.clone())
+        .expect("keysize is at least 1")
+        .decrypt(&cipherv);
+    VigSolveReport {
+        columns,
+        method: KeyMethod::MutualIc,
+        key,
+        plain,
+    }
+}
+
+pub fn keylength_candidates(
+    cipher: &str,
+    min_keylength: usize,
+    max_keylength: usize,
+) -> Vec<usize> {
+    //merge the kasiski, freidman 1 and freidman 2 guesses into one ranked
+    //list, each method's guesses are given points by rank (best gets the
+    //most) and the points are summed
+    let mut points: HashMap<usize, usize> = HashMap::new();
+    let kasiski = kasiski(cipher, min_keylength.max(2), max_keylength).scores;
+    let mut freidman: Vec<(usize, f64)> = freidman_1(cipher, min_keylength, max_keylength)
+        .averages()
+        .into_iter()
+        .map(|(k, avg)| (k, (avg - KP).abs()))
+        .collect();
+    freidman.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let estimate = freidman_2(cipher).estimate.max(1f64);
+    let ranked = [
+        kasiski.iter().take(3).map(|x| x.0).collect::<Vec<usize>>(),
+        freidman.iter().take(3).map(|x| x.0).collect(),
+        vec![
+            estimate.round() as usize,
+            estimate.floor() as usize,
+            estimate.ceil() as usize,
+        ],
+    ];
+    for guesses in ranked.iter() {
+        for (r, &k) in guesses.iter().enumerate() {
+            if k >= min_keylength.max(1) && k <= max_keylength {
+                *points.entry(k).or_insert(0) += 3 - r.min(3);
+            }
+        }
+    }
+    let mut candidates: Vec<(usize, usize)> = points.into_iter().collect();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    candidates.into_iter().map(|(k, _)| k).collect()
+}
+
+fn shortest_period(key: &[u8]) -> Vec<u8> {
+    //a key of 'abcabc' is the same as 'abc'
+    let l = key.len();
+    (1..=l)
+        .find(|&p| l.is_multiple_of(p) && (p..l).all(|i| key[i] == key[i - p]))
+        .map_or(key.to_vec(), |p| key[..p].to_vec())
+}
+
+#[derive(Clone, Debug)]
+pub struct VigenereSolution {
+    //best key found and it's decryption, confidence is how close the
+    //plaintext's quadgrams are to english (1) rather than random (0), so it
+    //drops when some of the key is wrong
+    pub key: Vec<u8>,
+    pub plain: Vec<u8>,
+    pub confidence: f64,
+}
+
+pub fn crack_vigenere(cipher: &str) -> VigenereSolution {
+    crack_vigenere_with(cipher, &LetterFreq::new())
+}
+
+fn explaining_divisor(cipherv: &[u8], k: usize) -> usize {
+    //the shortest keylength dividing k whose columns look as english as k's.
+    //If d is the keylength, k's columns are no better than d's, but if k is,
+    //d's columns mix k/d alphabets and their IC is only d/k of the way from
+    //random to english. So k has to get over a third of that gap above d
+    //(less than half, as a keylength with repeated letters like 'pineapples'
+    //has some columns of d which don't mix at all)
+    let phi = |k: usize| avg(&column_ics(cipherv, k));
+    let at_k = phi(k);
+    (1..k)
+        .filter(|&d| k.is_multiple_of(d))
+        .find(|&d| at_k - phi(d) < (KP - KR) * (1f64 - d as f64 / k as f64) / 3f64)
+        .unwrap_or(k)
+}
+
+pub fn crack_vigenere_with(cipher: &str, fitness: &dyn Fitness) -> VigenereSolution {
+    //solve the key for each of the candidate keylengths (both by columns on
+    //their own and by mutual index of coincidence) and keep the one whose
+    //decryption looks the most english (highest fitness). A longer key always
+    //fits a little better just by chance, as each column is fitted on it's
+    //own; so a candidate is first swapped for the shortest length dividing
+    //it which explains it's column IC just as well, and a longer key has to
+    //be clearly better (by 10%) to beat a shorter one
+    let cipherv = str_upper_to_int(cipher);
+    let mut lengths: Vec<usize> = Vec::new();
+    for k in keylength_candidates(cipher, 1, 20) {
+        let d = explaining_divisor(&cipherv, k);
+        if !lengths.contains(&d) {
+            lengths.push(d);
+        }
+    }
+    let mut best: Option<(f64, Vec<u8>, Vec<u8>)> = None;
+    for k in lengths {
+        for solved in [solve_vig(cipher, k), solve_vig_mic(cipher, k)].iter() {
+            let key = shortest_period(&solved.key);
+            let plain = Vigenere::new(key.clone())
+                .expect("keysize is at least 1")
+                .decrypt(&cipherv);
+            let score = fitness.fitness(&plain);
+            let better = match best {
+                Some((b, ref bkey, _)) if key.len() < bkey.len() => score >= b - b.abs() / 10f64,
+                Some((b, ref bkey, _)) if key.len() == bkey.len() => score > b,
+                Some((b, _, _)) => score > b + b.abs() / 10f64,
+                None => true,
+            };
+            if better {
+                best = Some((score, key, plain));
+            }
+        }
+    }
+    let (key, plain) = match best {
+        Some((_, key, plain)) => (key, plain),
+        None => (Vec::new(), cipherv),
+    };
+    let confidence = NgramModel::english(4).map_or(0f64, |m| m.confidence(&plain));
+    VigenereSolution {
+        key,
+        plain,
+        confidence,
+    }
+}
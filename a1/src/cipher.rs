@@ -0,0 +1,32 @@
+use alphabet::Alphabet;
+use text::Normalizer;
+
+pub trait Cipher {
+    //common interface for the classical ciphers, text is handled as ints
+    //(a=0, b=1...) as produced by text::str_upper_to_int
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8>;
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8>;
+
+    fn encrypt_str(&self, plain: &str) -> String {
+        //encrypt any text, keeping it's case and punctuation
+        let n = Normalizer::new().normalize(plain);
+        n.restore(&self.encrypt(&n.letters))
+    }
+
+    fn decrypt_str(&self, cipher: &str) -> String {
+        let n = Normalizer::new().normalize(cipher);
+        n.restore(&self.decrypt(&n.letters))
+    }
+
+    fn encrypt_in(&self, alphabet: &Alphabet, plain: &str) -> String {
+        //encrypt text using alphabet to convert it to ints, the cipher should
+        //be using the same modulus as the alphabet
+        let n = Normalizer::with_alphabet(alphabet.clone()).normalize(plain);
+        n.restore(&self.encrypt(&n.letters))
+    }
+
+    fn decrypt_in(&self, alphabet: &Alphabet, cipher: &str) -> String {
+        let n = Normalizer::with_alphabet(alphabet.clone()).normalize(cipher);
+        n.restore(&self.decrypt(&n.letters))
+    }
+}
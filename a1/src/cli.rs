@@ -0,0 +1,351 @@
+use std::fs;
+use std::io::{self, Read};
+
+use a1::json::Json;
+use a1::stats::analyze;
+use a1::substitution::crack_substitution;
+use a1::text::{clean, int_to_str_lower, str_upper_to_int};
+use a1::{
+    crack_affine_with, crack_hill, crack_vigenere, Affine, Cipher, Hill, Matrix, NgramModel, Shift,
+    Sign, SubstitutionCipher, Vigenere,
+};
+use a1::{identify, CipherType};
+
+pub const USAGE: &str = "usage:
+    a1                                      run the assignment questions
+    a1 encrypt <cipher> <key> [file]        encrypt a file (or stdin)
+    a1 decrypt <cipher> <key> [file]        decrypt a file (or stdin)
+    a1 crack <cipher> [file]                find the key and decrypt
+    a1 analyze [file]                       statistics of the text
+    a1 identify [file]                      guess which cipher was used
+
+ciphers and keys:
+    shift               3 or d
+    affine              5,8 (a,b)
+    vigenere            lemon
+    beaufort            lemon
+    variant-beaufort    lemon
+    autokey             lemon
+    hill                2,3,9,6 (the rows of a square matrix)
+    substitution        qwertyuiopasdfghjklzxcvbnm or a keyword
+    auto                (crack only) identify the cipher, then crack it
+
+options:
+    --json              print json instead of text
+    --crib <text>       (crack affine) text known to be in the plaintext
+    --plain <text>      (crack hill) known plaintext for the start of the text
+    --size <n>          (crack hill) size of the key matrix, default 2";
+
+struct Options {
+    //parsed command line, anything not an option is positional
+    json: bool,
+    crib: Option<String>,
+    plain: Option<String>,
+    size: usize,
+    positional: Vec<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut opts = Options {
+        json: false,
+        crib: None,
+        plain: None,
+        size: 2,
+        positional: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--crib" => opts.crib = Some(value("--crib")?),
+            "--plain" => opts.plain = Some(value("--plain")?),
+            "--size" => {
+                opts.size = value("--size")?
+                    .parse()
+                    .map_err(|_| "--size should be a number".to_owned())?
+            }
+            a if a.starts_with("--") => return Err(format!("unknown option {}", a)),
+            _ => opts.positional.push(arg.clone()),
+        }
+    }
+    Ok(opts)
+}
+
+fn read_input(path: Option<&String>) -> Result<String, String> {
+    //read from the file given, or stdin when there isn't one (or it's -)
+    match path.map(|p| p.as_str()) {
+        None | Some("-") => {
+            let mut s = String::new();
+            io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("couldn't read stdin: {}", e))?;
+            Ok(s)
+        }
+        Some(p) => fs::read_to_string(p).map_err(|e| format!("couldn't read {}: {}", p, e)),
+    }
+}
+
+fn numbers(key: &str) -> Result<Vec<isize>, String> {
+    key.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            x.parse()
+                .map_err(|_| format!("'{}' in the key isn't a number", x))
+        })
+        .collect()
+}
+
+fn letters(key: &str) -> Result<Vec<u8>, String> {
+    let key = str_upper_to_int(key);
+    if key.is_empty() {
+        return Err("the key needs some letters".to_owned());
+    }
+    Ok(key)
+}
+
+fn matrix_str(m: &Matrix) -> String {
+    m.data
+        .iter()
+        .flat_map(|row| row.iter())
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn build_cipher(name: &str, key: &str) -> Result<Box<dyn Cipher>, String> {
+    //turn a cipher name and key from the command line into a cipher
+    let cipher: Box<dyn Cipher> = match name {
+        "shift" | "caesar" => {
+            let k = match key.parse::<isize>() {
+                Ok(k) => k.rem_euclid(26) as u8,
+                Err(_) => letters(key)?[0],
+            };
+            Box::new(Shift::new(k))
+        }
+        "affine" => match numbers(key)?.as_slice() {
+            &[a, b] => Box::new(
+                Affine::new(a.rem_euclid(26) as u8, b.rem_euclid(26) as u8)
+                    .ok_or_else(|| format!("{} has no inverse mod 26", a))?,
+            ),
+            _ => return Err("affine keys are two numbers, a,b".to_owned()),
+        },
+        "vigenere" | "beaufort" | "variant-beaufort" | "autokey" => {
+            let sign = match name {
+                "beaufort" => Sign::Beaufort,
+                "variant-beaufort" => Sign::VariantBeaufort,
+                _ => Sign::Vigenere,
+            };
+            let cipher = if name == "autokey" {
+                Vigenere::autokey(letters(key)?, sign)
+            } else {
+                Vigenere::with_sign(letters(key)?, sign)
+            };
+            Box::new(cipher.ok_or("the key needs some letters")?)
+        }
+        "hill" => {
+            let key = if key.chars().all(|c| c.is_alphabetic()) {
+                letters(key)?.into_iter().map(|x| x as isize).collect()
+            } else {
+                numbers(key)?
+            };
+            let n = (1..=key.len()).find(|&n| n * n >= key.len()).unwrap_or(0);
+            if n == 0 || n * n != key.len() {
+                return Err("hill keys need a square number of values".to_owned());
+            }
+            let m = Matrix {
+                data: key.chunks(n).map(|row| row.to_vec()).collect(),
+            };
+            Box::new(Hill::new(m).ok_or("the hill key has no inverse mod 26")?)
+        }
+        "substitution" => Box::new(
+            SubstitutionCipher::from_letters(key)
+                .unwrap_or_else(|| SubstitutionCipher::keyword(key)),
+        ),
+        "lfsr" => return Err("lfsr is in a2".to_owned()),
+        _ => return Err(format!("unknown cipher {}", name)),
+    };
+    Ok(cipher)
+}
+
+fn render(fields: Vec<(&str, Json)>, json: bool) -> String {
+    //the fields as a json object, or one 'name: value' line each
+    if json {
+        return Json::obj(fields).to_string();
+    }
+    fields
+        .into_iter()
+        .map(|(k, v)| match v {
+            Json::Str(s) => format!("{}: {}", k, s),
+            v => format!("{}: {}", k, v),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn crack(name: &str, text: &str, opts: &Options) -> Result<Vec<(&'static str, Json)>, String> {
+    let cipher = clean(text);
+    let quadgrams = || NgramModel::english(4).expect("bundled quadgrams");
+    let fields = match name {
+        "shift" | "caesar" => {
+            let quadgrams = quadgrams();
+            let k = (0..26u8)
+                .max_by(|&a, &b| {
+                    let score = |k: u8| quadgrams.score(&Shift::new(k).decrypt(&cipher));
+                    score(a).total_cmp(&score(b))
+                })
+                .unwrap_or(0);
+            vec![
+                ("key", Json::Num(k as f64)),
+                ("plaintext", Json::Str(Shift::new(k).decrypt_str(text))),
+            ]
+        }
+        "affine" => {
+            let crib = opts.crib.as_ref().map(|c| str_upper_to_int(c));
+            let best = crack_affine_with(&cipher, 1, crib.as_ref().map(|c| &c[..]), &quadgrams());
+            let (key, score) = best.into_iter().next().ok_or("no key fits the crib")?;
+            vec![
+                ("key", Json::str(&format!("{},{}", key.a, key.b))),
+                ("score", Json::Num(score)),
+                ("plaintext", Json::Str(key.decrypt_str(text))),
+            ]
+        }
+        "vigenere" => {
+            let solved = crack_vigenere(text);
+            vec![
+                ("key", Json::Str(int_to_str_lower(&solved.key))),
+                ("confidence", Json::Num(solved.confidence)),
+                (
+                    "plaintext",
+                    Json::Str(
+                        Vigenere::new(solved.key).map_or(text.to_owned(), |v| v.decrypt_str(text)),
+                    ),
+                ),
+            ]
+        }
+        "substitution" => {
+            let solved = crack_substitution(&cipher);
+            vec![
+                ("key", Json::Str(int_to_str_lower(&solved.key.key))),
+                ("score", Json::Num(solved.score)),
+                ("plaintext", Json::Str(solved.key.decrypt_str(text))),
+            ]
+        }
+        "hill" => {
+            let plain = opts
+                .plain
+                .as_ref()
+                .ok_or("cracking hill needs known plaintext (--plain)")?;
+            if opts.size == 0 {
+                return Err("--size should be at least 1".to_owned());
+            }
+            let hill = crack_hill(&str_upper_to_int(plain), &cipher, opts.size)
+                .ok_or("no key found from the known plaintext")?;
+            vec![
+                ("key", Json::Str(matrix_str(&hill.key))),
+                ("plaintext", Json::Str(hill.decrypt_str(text))),
+            ]
+        }
+        _ => return Err(format!("can't crack {}", name)),
+    };
+    Ok(fields)
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    //run a command line (without the program name), giving what to print
+    let command = args.first().map(|c| c.as_str()).unwrap_or("help");
+    let opts = parse_options(&args[1.min(args.len())..])?;
+    let pos = &opts.positional;
+    match command {
+        "encrypt" | "decrypt" => {
+            if pos.len() < 2 || pos.len() > 3 {
+                return Err(format!("{} needs a cipher and a key", command));
+            }
+            let cipher = build_cipher(&pos[0], &pos[1])?;
+            let text = read_input(pos.get(2))?;
+            let (field, out) = if command == "encrypt" {
+                ("ciphertext", cipher.encrypt_str(&text))
+            } else {
+                ("plaintext", cipher.decrypt_str(&text))
+            };
+            if opts.json {
+                Ok(render(
+                    vec![
+                        ("cipher", Json::str(&pos[0])),
+                        ("key", Json::str(&pos[1])),
+                        (field, Json::Str(out)),
+                    ],
+                    true,
+                ))
+            } else {
+                Ok(out)
+            }
+        }
+        "crack" => {
+            if pos.is_empty() || pos.len() > 2 {
+                return Err("crack needs a cipher".to_owned());
+            }
+            let text = read_input(pos.get(1))?;
+            if clean(&text).is_empty() {
+                return Err("there are no letters to crack".to_owned());
+            }
+            let name = if pos[0] == "auto" {
+                //most likely cipher we can crack, hill only with a known
+                //plaintext and there's nothing for transposition
+                identify(&text)
+                    .ranked
+                    .into_iter()
+                    .map(|(t, _)| t)
+                    .find(|&t| match t {
+                        CipherType::Transposition => false,
+                        CipherType::Hill => opts.plain.is_some(),
+                        _ => true,
+                    })
+                    .map_or("substitution", |t| t.name())
+            } else {
+                &pos[0]
+            };
+            let mut fields = vec![("cipher", Json::str(name))];
+            fields.extend(crack(name, &text, &opts)?);
+            Ok(render(fields, opts.json))
+        }
+        "analyze" => {
+            if pos.len() > 1 {
+                return Err("analyze takes at most one file".to_owned());
+            }
+            let report = analyze(&read_input(pos.first())?, 20);
+            if opts.json {
+                Ok(report.to_json().to_string())
+            } else {
+                Ok(report.to_string())
+            }
+        }
+        "identify" => {
+            if pos.len() > 1 {
+                return Err("identify takes at most one file".to_owned());
+            }
+            let report = identify(&read_input(pos.first())?);
+            if opts.json {
+                let ranked = report
+                    .ranked
+                    .iter()
+                    .map(|&(t, p)| {
+                        Json::obj(vec![
+                            ("cipher", Json::str(t.name())),
+                            ("likelihood", Json::Num(p)),
+                        ])
+                    })
+                    .collect();
+                Ok(render(vec![("ranked", Json::Arr(ranked))], true))
+            } else {
+                Ok(report.to_string())
+            }
+        }
+        "help" | "--help" | "-h" => Ok(USAGE.to_owned()),
+        _ => Err(format!("unknown command {}", command)),
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use freq::{letter_counts, LetterFreq};
+
+pub trait Fitness {
+    //how much text (as ints) looks like the language, higher is better
+    fn fitness(&self, text: &[u8]) -> f64;
+}
+
+impl Fitness for LetterFreq {
+    fn fitness(&self, text: &[u8]) -> f64 {
+        -self.score(&letter_counts(text))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NgramModel {
+    //log10 probabilities of every n letter sequence, stored densely with the
+    //ngram read as a base 26 number (so AAAA=0, AAAB=1...). ngrams never seen
+    //are given a floor probability of 0.01/total
+    pub n: usize,
+    table: Vec<f64>,
+}
+
+impl NgramModel {
+    pub fn english(n: usize) -> Option<NgramModel> {
+        //bundled english bigram, trigram and quadgram counts (see data/)
+        let counts = match n {
+            2 => include_str!("../data/english_bigrams.txt"),
+            3 => include_str!("../data/english_trigrams.txt"),
+            4 => include_str!("../data/english_quadgrams.txt"),
+            _ => return None,
+        };
+        Some(NgramModel::parse(counts).expect("bad bundled ngram counts"))
+    }
+
+    pub fn parse(s: &str) -> io::Result<NgramModel> {
+        //read ngram counts, one '<ngram> <count>' per line with # for comments.
+        //every ngram must be the same length, and at most 5 letters as the
+        //table has an entry for all 26^n of them (11 million for n=5)
+        let bad = |i: usize, msg: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", i + 1, msg),
+            )
+        };
+        let mut counts: Vec<(usize, f64)> = Vec::new();
+        let mut n = 0;
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (gram, count) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(g), Some(c), None) => (g, c),
+                _ => return Err(bad(i, "expected '<ngram> <count>'")),
+            };
+            let count: f64 = count.parse().map_err(|_| bad(i, "bad count"))?;
+            if n == 0 {
+                n = gram.len();
+                if n > 5 {
+                    return Err(bad(i, "ngrams can be at most 5 letters"));
+                }
+            }
+            if gram.len() != n || !gram.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(bad(i, "ngrams must be letters and all the same length"));
+            }
+            let index = gram
+                .bytes()
+                .fold(0, |x, c| x * 26 + (c.to_ascii_uppercase() - b'A') as usize);
+            counts.push((index, count));
+        }
+        if n == 0 {
+            return Err(bad(0, "no ngrams found"));
+        }
+        let total: f64 = counts.iter().map(|&(_, c)| c).sum();
+        let mut table = vec![(0.01 / total).log10(); 26usize.pow(n as u32)];
+        for (index, count) in counts {
+            table[index] = (count / total).log10();
+        }
+        Ok(NgramModel { n, table })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<NgramModel> {
+        NgramModel::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn score(&self, text: &[u8]) -> f64 {
+        //log likelihood of the text, the sum of the log probability of every
+        //ngram in it. The ngram index is rolled along rather than recomputed
+        let size = self.table.len();
+        let mut index = 0;
+        let mut score = 0f64;
+        for (i, &c) in text.iter().enumerate() {
+            index = (index * 26 + c as usize) % size;
+            if i + 1 >= self.n {
+                score += self.table[index];
+            }
+        }
+        score
+    }
+
+    pub fn confidence(&self, text: &[u8]) -> f64 {
+        //how close the average log probability of text's ngrams is to the
+        //language's own average (1) rather than random letters' (0), both
+        //worked out from the table
+        let grams = (text.len() + 1).saturating_sub(self.n);
+        if grams == 0 {
+            return 0f64;
+        }
+        let language: f64 = self.table.iter().map(|&t| 10f64.powf(t) * t).sum();
+        let random = self.table.iter().sum::<f64>() / self.table.len() as f64;
+        ((self.score(text) / grams as f64 - random) / (language - random)).clamp(0f64, 1f64)
+    }
+}
+
+impl Fitness for NgramModel {
+    fn fitness(&self, text: &[u8]) -> f64 {
+        self.score(text)
+    }
+}
@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub fn ceaser_1(c: &mut [usize]) {
+    //ceaser shift the vector of items in place, shift by 1
+    //rather than shifting and re-counting, as we are maping A->B, B->C
+    //we can just manually take Z->A and just shuffle all by 1 position in the
+    //distribution vector.
+    c.rotate_right(1);
+}
+
+pub fn letter_counts(text: &[u8]) -> Vec<usize> {
+    //frequency distribution of text (as ints), first is occurences of a...
+    let mut freq = vec![0usize; 26];
+    for &c in text {
+        freq[c as usize] += 1;
+    }
+    freq
+}
+
+pub fn index_of_coincidence(text: &[u8]) -> f64 {
+    //chance two letters picked from the text are the same
+    let n = text.len();
+    if n < 2 {
+        return 0f64;
+    }
+    let f = letter_counts(text);
+    f.iter().fold(0, |k0, &f| k0 + (f * f.saturating_sub(1))) as f64 / ((n * (n - 1)) as f64)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub fn all() -> Vec<Language> {
+        vec![
+            Language::English,
+            Language::German,
+            Language::French,
+            Language::Spanish,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::German => "german",
+            Language::French => "french",
+            Language::Spanish => "spanish",
+        }
+    }
+
+    fn profile(self) -> &'static str {
+        //bundled letter frequency tables (see data/)
+        match self {
+            Language::English => include_str!("../data/english.freq"),
+            Language::German => include_str!("../data/german.freq"),
+            Language::French => include_str!("../data/french.freq"),
+            Language::Spanish => include_str!("../data/spanish.freq"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Scorer {
+    //how a letter distribution is compared against the expected one, all of
+    //them are lower is better
+    //pearson's x2; sum of (observed-expected)^2/expected
+    #[default]
+    ChiSquared,
+    //likelihood ratio; 2*sum of observed*ln(observed/expected)
+    GTest,
+    //1-cos of the angle between the two distributions (as vectors), ignores
+    //the length of the text entirely
+    Cosine,
+}
+
+impl Scorer {
+    pub fn all() -> Vec<Scorer> {
+        vec![Scorer::ChiSquared, Scorer::GTest, Scorer::Cosine]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Scorer::ChiSquared => "x2",
+            Scorer::GTest => "g-test",
+            Scorer::Cosine => "cosine",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Letter {
+    //A letter and it's frequency in the language
+    pub letter: char,
+    pub freq: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LetterFreq {
+    //set of letters and their frequency in a language, in alphabet order, and
+    //how to score text against them
+    pub freq: Vec<Letter>,
+    pub scorer: Scorer,
+}
+
+impl LetterFreq {
+    pub fn new() -> LetterFreq {
+        //frequency distribution of english words
+        LetterFreq::language(Language::English)
+    }
+
+    pub fn language(lang: Language) -> LetterFreq {
+        LetterFreq::parse(lang.profile()).expect("bad bundled frequency table")
+    }
+
+    pub fn parse(s: &str) -> io::Result<LetterFreq> {
+        //read a frequency table, one '<letter> <frequency>' per line with #
+        //for comments. Letters should be in the order of the alphabet used.
+        let mut freq = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let letter = parts.next().and_then(|l| {
+                let mut c = l.chars();
+                match (c.next(), c.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            });
+            let value = parts.next().and_then(|f| f.parse::<f64>().ok());
+            match (letter, value, parts.next()) {
+                (Some(letter), Some(f), None) => freq.push(Letter { letter, freq: f }),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: expected '<letter> <frequency>'", i + 1),
+                    ))
+                }
+            }
+        }
+        if freq.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "no letters in frequency table",
+            ));
+        }
+        Ok(LetterFreq {
+            freq,
+            scorer: Scorer::default(),
+        })
+    }
+
+    pub fn with_scorer(mut self, scorer: Scorer) -> LetterFreq {
+        self.scorer = scorer;
+        self
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<LetterFreq> {
+        //read a frequency table from a file
+        LetterFreq::parse(&fs::read_to_string(path)?)
+    }
+
+    // lookup a single character for distribution, probably never be used as
+    //ints will be used anyway. Plus freq dist is in order so...
+    /*fn lookup(&self,c:&char)->f64{
+        //will panic if bad char
+        let mut f = self.freq.clone();
+        f.retain(|l| l.letter==*c);
+        f[0].freq
+    }*/
+
+    pub fn lookup_n(&self, c: usize) -> f64 {
+        //lookup distribution of letter in 'c' place in alphabet
+        self.freq[c].freq
+    }
+
+    pub fn get_expected_dist(&self, n: usize) -> Vec<f64> {
+        //get expected distribution of letter given the size of text, not
+        //rounded as short texts expect fractions of most letters
+        (0..self.freq.len())
+            .map(|i| self.lookup_n(i) * n as f64)
+            .collect()
+    }
+
+    pub fn get_x2(&self, n: usize, dist: &[usize]) -> f64 {
+        //pearson's chi squared of the actual distribution of letters against
+        //the expected distribution. Letters which are never expected are
+        //skipped rather than dividing by zero
+        let expected = self.get_expected_dist(n);
+        dist.iter()
+            .zip(expected.iter())
+            .filter(|&(_, &e)| e > 0f64)
+            .fold(0f64, |x2, (&d, &e)| x2 + (d as f64 - e).powi(2) / e)
+    }
+
+    pub fn get_g(&self, n: usize, dist: &[usize]) -> f64 {
+        //g-test statistic, letters which don't show up add nothing
+        let expected = self.get_expected_dist(n);
+        2f64 * dist
+            .iter()
+            .zip(expected.iter())
+            .filter(|&(&d, &e)| d > 0 && e > 0f64)
+            .fold(0f64, |g, (&d, &e)| g + d as f64 * (d as f64 / e).ln())
+    }
+
+    pub fn get_cosine(&self, dist: &[usize]) -> f64 {
+        //cosine similarity between the actual and expected distributions,
+        //1 is identical, 0 is nothing in common
+        let dot = dist
+            .iter()
+            .zip(self.freq.iter())
+            .fold(0f64, |x, (&d, l)| x + d as f64 * l.freq);
+        let norm_d = dist.iter().fold(0f64, |x, &d| x + (d * d) as f64).sqrt();
+        let norm_e = self
+            .freq
+            .iter()
+            .fold(0f64, |x, l| x + l.freq * l.freq)
+            .sqrt();
+        if norm_d == 0f64 || norm_e == 0f64 {
+            return 0f64;
+        }
+        dot / (norm_d * norm_e)
+    }
+
+    pub fn score(&self, dist: &[usize]) -> f64 {
+        //score a distribution with the chosen scorer, lower is closer to
+        //the language
+        let n: usize = dist.iter().sum();
+        match self.scorer {
+            Scorer::ChiSquared => self.get_x2(n, dist),
+            Scorer::GTest => self.get_g(n, dist),
+            Scorer::Cosine => 1f64 - self.get_cosine(dist),
+        }
+    }
+
+    pub fn get_best_x2_shift(&self, mut dist: Vec<usize>) -> u8 {
+        //finds the shift which scores best (by the chosen scorer)
+        let mut best_i = 0u8;
+        let mut best = f64::INFINITY;
+        for i in 0..self.freq.len() as u8 {
+            let score = self.score(&dist);
+            if score < best {
+                best = score;
+                best_i = i;
+            }
+            ceaser_1(&mut dist);
+        }
+        best_i
+    }
+}
+
+impl Default for LetterFreq {
+    fn default() -> LetterFreq {
+        LetterFreq::new()
+    }
+}
+
+pub fn identify_language(plain: &[u8]) -> Vec<(Language, f64)> {
+    //rank the bundled languages by how well (decrypted) text matches their
+    //letter frequencies, best (lowest x2) first
+    let dist = letter_counts(plain);
+    let mut ranked: Vec<(Language, f64)> = Language::all()
+        .into_iter()
+        .map(|l| (l, LetterFreq::language(l).score(&dist)))
+        .collect();
+    ranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+    ranked
+}
@@ -0,0 +1,164 @@
+use cipher::Cipher;
+use matrix::Matrix;
+use modular::{modb_inv_n, modb_n};
+
+#[derive(Clone, Debug)]
+pub struct Hill {
+    //hill cipher with a nxn key and it's (mod m) inverse, text which isn't a
+    //multiple of n is padded out with pad (x by default)
+    pub key: Matrix,
+    pub inv: Matrix,
+    pub pad: u8,
+    pub m: usize,
+}
+
+impl Hill {
+    pub fn new(key: Matrix) -> Option<Hill> {
+        Hill::with_modulus(key, 26)
+    }
+
+    pub fn with_modulus(key: Matrix, m: usize) -> Option<Hill> {
+        //keys without an inverse can't be decoded, so are rejected, as is an
+        //empty key (nothing is a multiple of 0 so padding never ends) and an
+        //empty alphabet
+        if key.data.is_empty() || m == 0 {
+            return None;
+        }
+        let inv = key.inverse_mod(m)?;
+        let key = Matrix {
+            data: key
+                .data
+                .iter()
+                .map(|r| r.iter().map(|&x| modb_n(x, m)).collect())
+                .collect(),
+        };
+        Some(Hill {
+            key,
+            inv,
+            pad: (23 % m) as u8,
+            m,
+        })
+    }
+
+    pub fn pad(&self, text: &[u8]) -> Vec<u8> {
+        //pad text out to a multiple of n
+        let mut text = text.to_vec();
+        while !text.len().is_multiple_of(self.n()) {
+            text.push(self.pad);
+        }
+        text
+    }
+
+    pub fn n(&self) -> usize {
+        self.key.data.len()
+    }
+
+    fn apply(&self, k: &Matrix, text: &[u8]) -> Vec<u8> {
+        //multiply each n sized block of text by k, cipher text should always
+        //be a multiple of n but is padded the same as plaintext if not
+        Matrix::from_ints(&self.pad(text), self.n())
+            .iter()
+            .flat_map(|b| k.mul_mod(b, self.m).to_ints())
+            .collect()
+    }
+}
+
+impl Cipher for Hill {
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        self.apply(&self.key, plain)
+    }
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        self.apply(&self.inv, cipher)
+    }
+}
+
+fn prime_factors(m: usize) -> Vec<usize> {
+    //distinct primes dividing m, e.g. 26 -> [2, 13]
+    let mut m = m;
+    let mut primes = Vec::new();
+    let mut p = 2;
+    while p * p <= m {
+        if m.is_multiple_of(p) {
+            primes.push(p);
+            while m.is_multiple_of(p) {
+                m /= p;
+            }
+        }
+        p += 1;
+    }
+    if m > 1 {
+        primes.push(m);
+    }
+    primes
+}
+
+fn reduce(basis: &[Vec<isize>], v: &[u8], p: usize) -> Vec<isize> {
+    //what's left of v (mod p) after taking away the basis vectors, each has a
+    //leading 1 in a different place so it's zero there afterwards
+    let mut v: Vec<isize> = v.iter().map(|&x| modb_n(x as isize, p)).collect();
+    for b in basis {
+        let lead = b.iter().position(|&x| x != 0).unwrap_or(0);
+        let f = v[lead];
+        for (x, y) in v.iter_mut().zip(b) {
+            *x = modb_n(*x - f * y, p);
+        }
+    }
+    v
+}
+
+pub fn crack_hill(plain: &[u8], cipher: &[u8], n: usize) -> Option<Hill> {
+    //known plaintext attack, as C = K*P for any n blocks (P being the blocks
+    //as columns), K = C*P^-1 as long as P has an inverse. P is built up a
+    //block at a time, only keeping blocks that are independent of the ones
+    //already kept mod each prime factor of 26, as then P is invertible mod 26
+    //without having to try every set of n blocks.
+    if n == 0 {
+        return None;
+    }
+    let l = plain.len().min(cipher.len()) / n * n;
+    let p_blocks: Vec<&[u8]> = plain[..l].chunks(n).collect();
+    let c_blocks: Vec<&[u8]> = cipher[..l].chunks(n).collect();
+    let cols = |blocks: &[&[u8]], set: &[usize]| {
+        Matrix {
+            data: set
+                .iter()
+                .map(|&i| blocks[i].iter().map(|&x| x as isize).collect())
+                .collect(),
+        }
+        .transpose()
+    };
+    let primes = prime_factors(26);
+    let mut bases: Vec<Vec<Vec<isize>>> = vec![Vec::new(); primes.len()];
+    let mut set = Vec::new();
+    for (i, block) in p_blocks.iter().enumerate() {
+        if set.len() == n {
+            break;
+        }
+        let left: Vec<Vec<isize>> = primes
+            .iter()
+            .zip(&bases)
+            .map(|(&p, basis)| reduce(basis, block, p))
+            .collect();
+        if left.iter().all(|v| v.iter().any(|&x| x != 0)) {
+            for ((&p, basis), v) in primes.iter().zip(bases.iter_mut()).zip(left) {
+                //scale so the leading value is 1
+                let lead = v.iter().find(|&&x| x != 0).map_or(1, |&x| x);
+                let inv = modb_inv_n(lead, p);
+                basis.push(v.iter().map(|&x| modb_n(x * inv, p)).collect());
+            }
+            set.push(i);
+        }
+    }
+    if set.len() < n {
+        return None;
+    }
+    //the key found is the only one that fits these blocks, so if it doesn't
+    //fit the rest there isn't a key
+    let p_inv = cols(&p_blocks, &set).inverse()?;
+    let hill = Hill::new(cols(&c_blocks, &set).mul(&p_inv))?;
+    if hill.encrypt(&plain[..l]) == cipher[..l] {
+        Some(hill)
+    } else {
+        None
+    }
+}
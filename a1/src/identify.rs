@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use affine::crack_affine;
+use analysis::{column_ics, KP, KR};
+use freq::{index_of_coincidence, letter_counts, LetterFreq};
+use text::clean;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CipherType {
+    Transposition,
+    Shift,
+    Affine,
+    Substitution,
+    Vigenere,
+    Hill,
+}
+
+impl CipherType {
+    pub fn all() -> Vec<CipherType> {
+        vec![
+            CipherType::Transposition,
+            CipherType::Shift,
+            CipherType::Affine,
+            CipherType::Substitution,
+            CipherType::Vigenere,
+            CipherType::Hill,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        //same names as the command line uses
+        match self {
+            CipherType::Transposition => "transposition",
+            CipherType::Shift => "shift",
+            CipherType::Affine => "affine",
+            CipherType::Substitution => "substitution",
+            CipherType::Vigenere => "vigenere",
+            CipherType::Hill => "hill",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IdentifyReport {
+    //the features used, each squashed to 0 (no) to 1 (yes), and every cipher
+    //type with how likely it looks (they sum to 1), best first. Text without
+    //any letters has nothing ranked
+    pub letters: usize,
+    pub ic: f64,
+    pub periodic_ic: f64,
+    pub doubled: f64,
+    pub mono: f64,
+    pub shuffled: f64,
+    pub periodic: f64,
+    pub english_plain: f64,
+    pub english_shift: f64,
+    pub english_affine: f64,
+    pub ranked: Vec<(CipherType, f64)>,
+}
+
+impl IdentifyReport {
+    pub fn best(&self) -> Option<CipherType> {
+        self.ranked.first().map(|&(t, _)| t)
+    }
+}
+
+fn squash(x: f64, no: f64, yes: f64) -> f64 {
+    //where x is between no and yes, clamped to 0..1
+    ((x - no) / (yes - no)).clamp(0f64, 1f64)
+}
+
+pub fn doubled_letters(text: &[u8]) -> f64 {
+    //fraction of neighbouring letters which are the same. Any shuffle of a
+    //text is about it's IC, english usually about half that (0.035 against
+    //0.067) though a paragraph with a lot of doubled letters gets near it
+    if text.len() < 2 {
+        return 0f64;
+    }
+    text.windows(2).filter(|w| w[0] == w[1]).count() as f64 / (text.len() - 1) as f64
+}
+
+pub fn identify(cipher: &str) -> IdentifyReport {
+    //Guess the cipher from statistics of the ciphertext. An english IC means
+    //monoalphabetic, then the best x2 of no key, a shift, and an affine key
+    //tells which (transposed english fits with no key at all, and a
+    //substitution is whatever none of them fit). A low IC means vigenere
+    //when some period's columns are clearly more english than the whole
+    //text, otherwise hill, which pads the text to a multiple of it's size.
+    //Every type gets the product of how well each feature fits it
+    let text = clean(cipher);
+    let n = text.len();
+    if n == 0 {
+        return IdentifyReport {
+            letters: 0,
+            ic: 0f64,
+            periodic_ic: 0f64,
+            doubled: 0f64,
+            mono: 0f64,
+            shuffled: 0f64,
+            periodic: 0f64,
+            english_plain: 0f64,
+            english_shift: 0f64,
+            english_affine: 0f64,
+            ranked: Vec::new(),
+        };
+    }
+    let ic = index_of_coincidence(&text);
+    //periods up to 20, as long as the columns get 10 or so letters
+    let periodic_ic = (2..=20.min(n / 10).max(2))
+        .map(|k| column_ics(&text, k).iter().sum::<f64>() / k as f64)
+        .fold(0f64, f64::max);
+    let doubled = doubled_letters(&text);
+
+    let lf = LetterFreq::new();
+    let candidates = crack_affine(&text, 312, None, None);
+    let x2_shift = candidates
+        .iter()
+        .filter(|c| c.key.a == 1)
+        .map(|c| c.x2)
+        .fold(f64::INFINITY, f64::min);
+    let x2_affine = candidates.first().map_or(f64::INFINITY, |c| c.x2);
+    let x2_plain = lf.get_x2(n, &letter_counts(&text));
+    //roughly the largest x2 english text gets, anything over it is less
+    //english the bigger it is
+    let limit = 60f64 + 0.1 * n as f64;
+    let english = |x2: f64| (limit / x2).min(1f64).powi(2);
+
+    let mono = squash(ic, KR, KP - 0.005);
+    //transposed text should have about as many doubled letters as it's IC.
+    //Only used for transposition, english can have that many too so a low
+    //count rules transposition out but a high one doesn't rule it in
+    let shuffled = squash(doubled / ic.max(KR), 0.4, 0.8);
+    //any text has some period which looks a little better by chance, and a
+    //monoalphabetic one has english columns at every period anyway
+    let periodic = squash(periodic_ic - ic, (KP - KR) / 4f64, (KP - KR) / 2f64);
+    let english_plain = english(x2_plain);
+    let english_shift = english(x2_shift);
+    let english_affine = english(x2_affine);
+    let padded = if n.is_multiple_of(2) || n.is_multiple_of(3) {
+        1f64
+    } else {
+        0.1
+    };
+
+    let mut ranked: Vec<(CipherType, f64)> = CipherType::all()
+        .into_iter()
+        .map(|t| {
+            let fits = match t {
+                CipherType::Transposition => vec![mono, shuffled, english_plain],
+                CipherType::Shift => vec![mono, 1f64 - english_plain, english_shift],
+                CipherType::Affine => vec![mono, 1f64 - english_shift, english_affine],
+                CipherType::Substitution => vec![mono, 1f64 - english_affine],
+                CipherType::Vigenere => vec![1f64 - mono, periodic],
+                CipherType::Hill => vec![1f64 - mono, 1f64 - periodic, padded],
+            };
+            //floored so one bad feature doesn't rule a type out completely
+            (t, fits.iter().map(|f| f.max(0.01)).product::<f64>())
+        })
+        .collect();
+    let total: f64 = ranked.iter().map(|&(_, p)| p).sum();
+    for r in ranked.iter_mut() {
+        r.1 /= total;
+    }
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    IdentifyReport {
+        letters: n,
+        ic,
+        periodic_ic,
+        doubled,
+        mono,
+        shuffled,
+        periodic,
+        english_plain,
+        english_shift,
+        english_affine,
+        ranked,
+    }
+}
+
+impl fmt::Display for IdentifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Identify----")?;
+        writeln!(f, "Letters; {}", self.letters)?;
+        writeln!(
+            f,
+            "IC; {:.4}, best periodic IC; {:.4}, doubled letters; {:.4}",
+            self.ic, self.periodic_ic, self.doubled
+        )?;
+        writeln!(
+            f,
+            "Monoalphabetic; {:.2}, shuffled; {:.2}, periodic; {:.2}",
+            self.mono, self.shuffled, self.periodic
+        )?;
+        writeln!(
+            f,
+            "English with no key; {:.2}, a shift; {:.2}, an affine key; {:.2}",
+            self.english_plain, self.english_shift, self.english_affine
+        )?;
+        if self.ranked.is_empty() {
+            writeln!(f, "No letters to go on")?;
+        } else {
+            writeln!(f, "Likely ciphers;")?;
+        }
+        for &(t, p) in self.ranked.iter() {
+            writeln!(f, "{:<14} {:.3}", t.name(), p)?;
+        }
+        writeln!(f, "----Identify DONE----")
+    }
+}
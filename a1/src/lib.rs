@@ -0,0 +1,27 @@
+pub mod affine;
+pub mod alphabet;
+pub mod analysis;
+pub mod cipher;
+pub mod fitness;
+pub mod freq;
+pub mod hill;
+pub mod identify;
+pub mod json;
+pub mod matrix;
+pub mod modular;
+pub mod stats;
+pub mod substitution;
+pub mod text;
+pub mod vigenere;
+pub mod wordlist;
+
+pub use affine::{crack_affine, crack_affine_with, Affine, Shift};
+pub use alphabet::Alphabet;
+pub use analysis::{crack_vigenere, crack_vigenere_with, VigenereSolution};
+pub use cipher::Cipher;
+pub use fitness::{Fitness, NgramModel};
+pub use hill::{crack_hill, Hill};
+pub use identify::{identify, CipherType};
+pub use matrix::Matrix;
+pub use substitution::{crack_substitution, SubstitutionCipher};
+pub use vigenere::{Sign, Vigenere};
@@ -0,0 +1,173 @@
+use modular::{modb, modb_inv_n, modb_n};
+use text::from_int_lower;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    //Matrix set with a 2d array datastructure, using ints not uints for ease of
+    //fixing negatives (rather than ensuring they don't happen ever)
+    pub data: Vec<Vec<isize>>,
+}
+
+impl Matrix {
+    //create a new matrix from a 1d array (for importing from string)
+    pub fn new(v: Vec<isize>) -> Matrix {
+        Matrix {
+            data: v.into_iter().map(|x| vec![x]).collect(),
+        }
+    }
+
+    pub fn identity(n: usize) -> Matrix {
+        //nxn identity matrix
+        Matrix {
+            data: (0..n)
+                .map(|i| (0..n).map(|j| if i == j { 1 } else { 0 }).collect())
+                .collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.data.first().map_or(0, |r| r.len())
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.data.iter().all(|r| r.len() == self.rows())
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        Matrix {
+            data: (0..self.cols())
+                .map(|j| self.data.iter().map(|r| r[j]).collect())
+                .collect(),
+        }
+    }
+
+    pub fn from_ints(ints: &[u8], n: usize) -> Vec<Matrix> {
+        //convert ints to nx1 sized matricies for encode or decoding
+        ints.chunks(n)
+            .map(|x| Matrix::new(x.iter().map(|&v| v as isize).collect()))
+            .collect()
+    }
+
+    pub fn to_ints(&self) -> Vec<u8> {
+        //flatten the matrix back to a set of ints (row by row), values should
+        //already be reduced by the modulus
+        self.data
+            .iter()
+            .flat_map(|x| x.iter().map(|&xx| xx as u8))
+            .collect()
+    }
+
+    pub fn to_text_string(&self) -> String {
+        //for using into a readible string
+        self.to_ints().into_iter().map(from_int_lower).collect()
+    }
+
+    pub fn mul_n(&mut self, m: isize) {
+        //multiplies all fields by static amount m
+        for row in self.data.iter_mut() {
+            for x in row.iter_mut() {
+                *x = modb(*x * m);
+            }
+        }
+    }
+
+    pub fn mul(&self, rhs: &Self) -> Self {
+        self.mul_mod(rhs, 26)
+    }
+
+    pub fn mul_mod(&self, rhs: &Self, m: usize) -> Self {
+        //multiplies self (mxn) by rhs (nxp), giving a mxp matrix (mod m)
+        Matrix {
+            data: self
+                .data
+                .iter()
+                .map(|row| {
+                    (0..rhs.cols())
+                        .map(|k| {
+                            modb_n(
+                                row.iter()
+                                    .enumerate()
+                                    .fold(0isize, |sum, (i, &x)| sum + x * rhs.data[i][k]),
+                                m,
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Matrix> {
+        self.inverse_mod(26)
+    }
+
+    pub fn inverse_mod(&self, m: usize) -> Option<Matrix> {
+        //find the (mod m) inverse by Gauss-Jordan elimination on [self | I]
+        //as Zm isn't always a field, a column may have no invertible value to use
+        //as a pivot, so the rows are first combined euclid style (as in gcd)
+        //until only the pivot row has a non zero value in the column. If that
+        //value still has no inverse then neither does the matrix.
+        if !self.is_square() {
+            return None;
+        }
+        let n = self.rows();
+        let mut a: Vec<Vec<isize>> = self
+            .data
+            .iter()
+            .zip(Matrix::identity(n).data)
+            .map(|(r, i)| r.iter().map(|&x| modb_n(x, m)).chain(i).collect())
+            .collect();
+        for c in 0..n {
+            for r in (c + 1)..n {
+                while a[r][c] != 0 {
+                    let q = a[c][c] / a[r][c];
+                    sub_row(&mut a, c, r, q, m);
+                    a.swap(c, r);
+                }
+            }
+            let p = modb_inv_n(a[c][c], m);
+            if p < 0 {
+                return None;
+            }
+            for x in a[c].iter_mut() {
+                *x = modb_n(*x * p, m);
+            }
+            for r in 0..n {
+                if r != c && a[r][c] != 0 {
+                    let f = a[r][c];
+                    sub_row(&mut a, r, c, f, m);
+                }
+            }
+        }
+        Some(Matrix {
+            data: a.into_iter().map(|r| r[n..].to_vec()).collect(),
+        })
+    }
+
+    pub fn to_data_string(&self) -> String {
+        //to data string, to see underlying data of matricie
+        self.data.iter().fold(String::new(), |s, x| {
+            s + "| "
+                + &x.iter()
+                    .fold(String::new(), |ss, &xx| ss + &xx.to_string() + " ")
+                + "|\n"
+        })
+    }
+}
+
+fn sub_row(a: &mut [Vec<isize>], dst: usize, src: usize, f: isize, m: usize) {
+    //row operation dst = dst - f*src (mod m)
+    let src = a[src].clone();
+    for (x, y) in a[dst].iter_mut().zip(src) {
+        *x = modb_n(*x - f * y, m);
+    }
+}
+
+pub fn matricies_to_str(v: &[Matrix]) -> String {
+    //convert set of matricies to their human readible text
+    v.iter().fold(String::new(), |s, x| s + &x.to_text_string())
+}
@@ -0,0 +1,37 @@
+pub fn modb_n(x: isize, m: usize) -> isize {
+    //modulo by base n
+    let m = m as isize;
+    ((x % m) + m) % m
+}
+
+pub fn modb(x: isize) -> isize {
+    //modulo by base 26 (for alphabet)
+    modb_n(x, 26)
+}
+
+pub fn modb_inv(x: isize) -> isize {
+    //find the modulo inverse of x (mod26)
+    modb_inv_n(x, 26)
+}
+
+pub fn modb_inv_n(x: isize, m: usize) -> isize {
+    //find the modulo inverse of x
+    //fine j S.T. x*j==1, j must be less than m if it exists
+    let x = modb_n(x, m);
+    for j in 0..m as isize {
+        if modb_n(x * j, m) == 1 {
+            return j;
+        }
+    }
+    -1
+}
+
+pub fn gcd(a: usize, b: usize) -> usize {
+    //a is larger, b is smaller (after initial iteration)
+    //while the smaller (b) is not 0 (they remainders match)
+    //keep splitting into smaller pieces of remainders
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
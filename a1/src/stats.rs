@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use analysis::{column_ics, KP, KR};
+use freq::{index_of_coincidence, letter_counts, LetterFreq};
+use json::Json;
+use text::{clean, from_int_lower, int_to_str_lower};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CipherFamily {
+    //letters moved around but not changed
+    Transposition,
+    //every letter always changed to the same letter (shift, affine...)
+    Monoalphabetic,
+    //letters changed differently depending on where they are (vigenere,
+    //hill...), or something which doesn't look like language at all
+    Polyalphabetic,
+    //no letters to go on
+    Unknown,
+}
+
+impl CipherFamily {
+    pub fn name(self) -> &'static str {
+        match self {
+            CipherFamily::Transposition => "transposition",
+            CipherFamily::Monoalphabetic => "monoalphabetic",
+            CipherFamily::Polyalphabetic => "polyalphabetic",
+            CipherFamily::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Repeat {
+    //an ngram found more than once, where it was and the gaps between
+    pub ngram: String,
+    pub positions: Vec<usize>,
+    pub spacings: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StatsReport {
+    //statistics of a ciphertext (just it's letters), periodic is the average
+    //column IC for each period, x2 is against english without any shift, and
+    //entropy is in bits per letter
+    pub letters: usize,
+    pub counts: Vec<usize>,
+    pub ic: f64,
+    pub periodic: Vec<(usize, f64)>,
+    pub repeats: Vec<Repeat>,
+    pub entropy: f64,
+    pub x2: f64,
+    pub family: CipherFamily,
+    pub period: Option<usize>,
+}
+
+pub fn entropy(counts: &[usize]) -> f64 {
+    //shannon entropy of a distribution, in bits. english letters are about
+    //4.2, random letters log2(26) = 4.7
+    let n: usize = counts.iter().sum();
+    if n == 0 {
+        return 0f64;
+    }
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| c as f64 / n as f64)
+        .fold(0f64, |h, p| h - p * p.log2())
+}
+
+pub fn repeated_ngrams(text: &[u8], min: usize, max: usize) -> Vec<Repeat> {
+    //every ngram (min to max long, inclusive) found more than once, longest
+    //first then most common
+    let mut repeats: Vec<Repeat> = Vec::new();
+    for n in min.max(1)..=max {
+        let mut seen: HashMap<&[u8], Vec<usize>> = HashMap::new();
+        for (i, w) in text.windows(n).enumerate() {
+            seen.entry(w).or_default().push(i);
+        }
+        repeats.extend(
+            seen.into_iter()
+                .filter(|(_, p)| p.len() > 1)
+                .map(|(w, positions)| Repeat {
+                    ngram: int_to_str_lower(w),
+                    spacings: positions.windows(2).map(|p| p[1] - p[0]).collect(),
+                    positions,
+                }),
+        );
+    }
+    repeats.sort_by(|a, b| {
+        b.ngram
+            .len()
+            .cmp(&a.ngram.len())
+            .then(b.positions.len().cmp(&a.positions.len()))
+            .then(a.positions.cmp(&b.positions))
+    });
+    repeats
+}
+
+pub fn analyze(cipher: &str, max_period: usize) -> StatsReport {
+    //all the statistics of a ciphertext, and a guess at the kind of cipher.
+    //An english looking IC means each letter is always swapped for the same
+    //one; if the letters also have english frequencies they haven't been
+    //swapped at all, just moved. A low IC means polyalphabetic, and a period
+    //where the columns look english is probably it's keylength
+    let text = clean(cipher);
+    let counts = letter_counts(&text);
+    let ic = index_of_coincidence(&text);
+    let periodic: Vec<(usize, f64)> = (1..=max_period.min(text.len() / 2).max(1))
+        .map(|k| {
+            let ics = column_ics(&text, k);
+            (k, ics.iter().sum::<f64>() / k as f64)
+        })
+        .collect();
+    let x2 = LetterFreq::new().get_x2(text.len(), &counts);
+    //a period is only believed when it's columns are at least halfway from
+    //random to english, and clearly (a third of the way) more english than
+    //the whole text, otherwise any monoalphabetic text passes at period 2.
+    //The average IC of k columns of m letters wobbles by about
+    //sqrt(2*KP/k)/m more than the whole text's by chance, so the rise also
+    //has to be four times that (up to 20 periods get tested, so one of them
+    //passing three is common), and columns of less than 25 letters are
+    //skipped altogether. Without a period, the text is monoalphabetic if
+    //it's own IC is halfway
+    let halfway = (KP + KR) / 2f64;
+    let period = periodic
+        .iter()
+        .filter(|&&(k, _)| k > 1 && text.len() / k >= 25)
+        .find(|&&(k, p)| {
+            let m = (text.len() / k) as f64;
+            let wobble = (2f64 * KP / k as f64).sqrt() / m;
+            p >= halfway && p - ic >= ((KP - KR) / 3f64).max(4f64 * wobble)
+        })
+        .map(|&(k, _)| k);
+    let family = if text.is_empty() {
+        CipherFamily::Unknown
+    } else if period.is_some() || ic < halfway {
+        CipherFamily::Polyalphabetic
+    } else if x2 < 60f64 + 0.1 * text.len() as f64 {
+        CipherFamily::Transposition
+    } else {
+        CipherFamily::Monoalphabetic
+    };
+    StatsReport {
+        letters: text.len(),
+        entropy: entropy(&counts),
+        counts,
+        ic,
+        periodic,
+        repeats: repeated_ngrams(&text, 3, 6),
+        x2,
+        family,
+        period,
+    }
+}
+
+impl StatsReport {
+    pub fn to_json(&self) -> Json {
+        let nums = |v: &[usize]| Json::Arr(v.iter().map(|&x| Json::Num(x as f64)).collect());
+        Json::obj(vec![
+            ("letters", Json::Num(self.letters as f64)),
+            ("counts", nums(&self.counts)),
+            ("ic", Json::Num(self.ic)),
+            (
+                "periodic",
+                Json::Arr(
+                    self.periodic
+                        .iter()
+                        .map(|&(k, p)| {
+                            Json::obj(vec![("period", Json::Num(k as f64)), ("ic", Json::Num(p))])
+                        })
+                        .collect(),
+                ),
+            ),
+            (
+                "repeats",
+                Json::Arr(
+                    self.repeats
+                        .iter()
+                        .map(|r| {
+                            Json::obj(vec![
+                                ("ngram", Json::str(&r.ngram)),
+                                ("positions", nums(&r.positions)),
+                                ("spacings", nums(&r.spacings)),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+            ("entropy", Json::Num(self.entropy)),
+            ("x2", Json::Num(self.x2)),
+            ("family", Json::str(self.family.name())),
+            (
+                "period",
+                self.period.map_or(Json::Null, |p| Json::Num(p as f64)),
+            ),
+        ])
+    }
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "----Statistics----")?;
+        writeln!(f, "Letters; {}", self.letters)?;
+        writeln!(f, "Letter frequencies;")?;
+        let most = self.counts.iter().cloned().max().unwrap_or(0).max(1);
+        for (i, &c) in self.counts.iter().enumerate() {
+            writeln!(
+                f,
+                "{} {:>5} {}",
+                from_int_lower(i as u8),
+                c,
+                "#".repeat((c * 50).div_ceil(most))
+            )?;
+        }
+        writeln!(
+            f,
+            "Index of coincidence; {:.4} (english {:.4}, random {:.4})",
+            self.ic, KP, KR
+        )?;
+        writeln!(f, "Entropy; {:.3} bits per letter", self.entropy)?;
+        writeln!(f, "X^2 against english (no shift); {:.2}", self.x2)?;
+        writeln!(f, "Average column IC by period;")?;
+        for &(k, p) in self.periodic.iter() {
+            writeln!(f, "{:>3} {:.4}", k, p)?;
+        }
+        writeln!(f, "Repeated ngrams (positions; spacings);")?;
+        for r in self.repeats.iter().take(20) {
+            writeln!(f, "{} {:?}; {:?}", r.ngram, r.positions, r.spacings)?;
+        }
+        if self.repeats.len() > 20 {
+            writeln!(f, "... and {} more", self.repeats.len() - 20)?;
+        }
+        match self.period {
+            Some(p) => writeln!(f, "Cipher looks {}, with period {}", self.family.name(), p)?,
+            None => writeln!(f, "Cipher looks {}", self.family.name())?,
+        }
+        writeln!(f, "----Statistics DONE----")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use affine::Shift;
+    use cipher::Cipher;
+    use text::str_upper_to_int;
+    use vigenere::Vigenere;
+
+    //293 letters of 1984, shifting them by 7 used to give period 13
+    const ORWELL: &str = "Winston Smith, his chin nuzzled into his breast in an effort to escape the vile wind, slipped quickly through the glass doors of Victory Mansions, though not quickly enough to prevent a swirl of gritty dust from entering along with him. The hallway smelt of boiled cabbage and old rag mats. At one end of it a coloured poster, too large for indoor display, had been";
+
+    #[test]
+    fn shift_has_no_period() {
+        let report = analyze(&Shift::new(7).encrypt_str(ORWELL), 20);
+        assert_eq!(report.letters, 293);
+        assert_eq!(report.family, CipherFamily::Monoalphabetic);
+        assert_eq!(report.period, None);
+    }
+
+    #[test]
+    fn vigenere_period() {
+        let v = Vigenere::new(str_upper_to_int("CRYPTO")).unwrap();
+        let report = analyze(&v.encrypt_str(ORWELL), 20);
+        assert_eq!(report.family, CipherFamily::Polyalphabetic);
+        assert_eq!(report.period, Some(6));
+    }
+
+    #[test]
+    fn no_letters() {
+        let report = analyze("12, 34.", 20);
+        assert_eq!(report.letters, 0);
+        assert_eq!(report.family, CipherFamily::Unknown);
+        assert_eq!(report.period, None);
+    }
+}
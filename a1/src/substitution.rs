@@ -0,0 +1,170 @@
+use std::cmp::Reverse;
+
+use cipher::Cipher;
+use fitness::{Fitness, NgramModel};
+use freq::letter_counts;
+use text::str_upper_to_int;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubstitutionCipher {
+    //monoalphabetic substitution, key[p] is the cipher letter for plain letter
+    //p and inv is the reverse mapping. Works in any modulus (the key length,
+    //26 unless made with with_modulus)
+    pub key: Vec<u8>,
+    pub inv: Vec<u8>,
+}
+
+impl SubstitutionCipher {
+    pub fn new(key: Vec<u8>) -> Option<SubstitutionCipher> {
+        SubstitutionCipher::with_modulus(key, 26)
+    }
+
+    pub fn with_modulus(key: Vec<u8>, m: usize) -> Option<SubstitutionCipher> {
+        //key must be a permutation of 0..m, anything shorter would leave
+        //letters with nothing to encrypt to
+        if key.len() != m {
+            return None;
+        }
+        let mut inv = vec![u8::MAX; key.len()];
+        for (p, &c) in key.iter().enumerate() {
+            if c as usize >= key.len() || inv[c as usize] != u8::MAX {
+                return None;
+            }
+            inv[c as usize] = p as u8;
+        }
+        Some(SubstitutionCipher { key, inv })
+    }
+
+    pub fn from_letters(key: &str) -> Option<SubstitutionCipher> {
+        //the cipher alphabet written out, eg "QWERTYUIOPASDFGHJKLZXCVBNM"
+        SubstitutionCipher::new(str_upper_to_int(key))
+    }
+
+    pub fn keyword(word: &str) -> SubstitutionCipher {
+        //keyword cipher, the cipher alphabet is the keyword (without repeated
+        //letters) followed by the rest of the alphabet in order
+        let mut key: Vec<u8> = Vec::new();
+        for c in str_upper_to_int(word).into_iter().chain(0..26) {
+            if !key.contains(&c) {
+                key.push(c);
+            }
+        }
+        SubstitutionCipher::new(key).expect("keyword key is always a permutation")
+    }
+}
+
+impl Cipher for SubstitutionCipher {
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        plain.iter().map(|&p| self.key[p as usize]).collect()
+    }
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        cipher.iter().map(|&c| self.inv[c as usize]).collect()
+    }
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+    //small xorshift64 generator, plenty for shuffling keys
+    fn new(seed: u64) -> XorShift {
+        XorShift(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SubstitutionSolution {
+    //best key found, the plaintext it gives and it's fitness
+    pub key: SubstitutionCipher,
+    pub plain: Vec<u8>,
+    pub score: f64,
+}
+
+pub fn crack_substitution(cipher: &[u8]) -> SubstitutionSolution {
+    let quadgrams = NgramModel::english(4).expect("bundled quadgrams");
+    crack_substitution_with(cipher, &quadgrams, 20, 0x5eed)
+}
+
+pub fn crack_substitution_with(
+    cipher: &[u8],
+    fitness: &dyn Fitness,
+    restarts: usize,
+    seed: u64,
+) -> SubstitutionSolution {
+    //hill climb over decryption keys; swap every pair of letters in the key
+    //and keep any swap that improves the fitness, until no swap helps. As this
+    //gets stuck on local maximums it's restarted from random keys, the first
+    //start being the letters matched up by frequency order
+    let mut rng = XorShift::new(seed);
+    let mut best: Option<(Vec<u8>, f64)> = None;
+    for restart in 0..restarts.max(1) {
+        let mut inv = if restart == 0 {
+            frequency_guess(cipher)
+        } else {
+            let mut inv: Vec<u8> = (0..26).collect();
+            for i in (1..26).rev() {
+                inv.swap(i, rng.below(i + 1));
+            }
+            inv
+        };
+        let mut score = fitness.fitness(&apply(&inv, cipher));
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..26 {
+                for j in i + 1..26 {
+                    inv.swap(i, j);
+                    let s = fitness.fitness(&apply(&inv, cipher));
+                    if s > score {
+                        score = s;
+                        improved = true;
+                    } else {
+                        inv.swap(i, j);
+                    }
+                }
+            }
+        }
+        if best.as_ref().is_none_or(|&(_, b)| score > b) {
+            best = Some((inv, score));
+        }
+    }
+    let (inv, score) = best.expect("at least one restart");
+    let mut key = vec![0u8; 26];
+    for (c, &p) in inv.iter().enumerate() {
+        key[p as usize] = c as u8;
+    }
+    let key = SubstitutionCipher::new(key).expect("hill climbing keeps keys as permutations");
+    SubstitutionSolution {
+        plain: key.decrypt(cipher),
+        key,
+        score,
+    }
+}
+
+fn apply(inv: &[u8], cipher: &[u8]) -> Vec<u8> {
+    cipher.iter().map(|&c| inv[c as usize]).collect()
+}
+
+fn frequency_guess(cipher: &[u8]) -> Vec<u8> {
+    //decryption key mapping the most common cipher letter to e, the next to t
+    //and so on
+    let order = str_upper_to_int("ETAOINSHRDLCUMWFGYPBVKJXQZ");
+    let counts = letter_counts(cipher);
+    let mut by_count: Vec<u8> = (0..26).collect();
+    by_count.sort_by_key(|&c| Reverse(counts[c as usize]));
+    let mut inv = vec![0u8; 26];
+    for (&c, &p) in by_count.iter().zip(order.iter()) {
+        inv[c as usize] = p;
+    }
+    inv
+}
@@ -0,0 +1,252 @@
+use alphabet::Alphabet;
+
+fn char_to_int(r: char, sub: u8) -> Option<u8> {
+    //convert character to int a=0, b=1... if it's one of the 26 from sub
+    (r as u32)
+        .checked_sub(sub as u32)
+        .filter(|&i| i < 26)
+        .map(|i| i as u8)
+}
+
+pub fn int_to_char(i: u8, sub: u8) -> char {
+    //convert int to character
+    (i + sub) as char
+}
+
+pub fn to_int_upper(r: char) -> Option<u8> {
+    //convert to integer from upper case character, None for anything else
+    char_to_int(r, 65)
+}
+
+pub fn from_int_lower(i: u8) -> char {
+    //convert from int to char (lower case char)
+    int_to_char(i, 97)
+}
+
+pub fn str_upper_to_int(s: &str) -> Vec<u8> {
+    //convert string to a vector of ints, anything that isn't a letter is
+    //dropped (see Normalizer)
+    clean(s)
+}
+
+pub fn int_to_str_lower(c: &[u8]) -> String {
+    //vector of ints converted to lowercase string
+    c.iter().map(|&i| from_int_lower(i)).collect()
+}
+
+pub fn fold(c: char) -> Option<&'static str> {
+    //fold an accented (lower case) latin letter to plain ascii letter(s)
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'þ' => "th",
+        _ => return None,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Slot {
+    //a letter from the alphabet (and if it had to be upper cased to be found
+    //in the alphabet), a character from the alphabet without a case (eg
+    //space in mod29) or some other character from the original text
+    Letter(bool),
+    Caseless,
+    Other(char),
+}
+
+#[derive(Clone, Debug)]
+pub struct Normalizer {
+    //fold_accents turns é into e (rather than treating it as punctuation),
+    //keep_other keeps characters not in the alphabet so they can be put back
+    //on restore
+    pub fold_accents: bool,
+    pub keep_other: bool,
+    pub alphabet: Alphabet,
+}
+
+impl Normalizer {
+    pub fn new() -> Normalizer {
+        Normalizer::with_alphabet(Alphabet::latin())
+    }
+
+    pub fn strip() -> Normalizer {
+        //only the letters are kept
+        Normalizer {
+            keep_other: false,
+            ..Normalizer::new()
+        }
+    }
+
+    pub fn with_alphabet(alphabet: Alphabet) -> Normalizer {
+        Normalizer {
+            fold_accents: true,
+            keep_other: true,
+            alphabet,
+        }
+    }
+
+    pub fn normalize(&self, s: &str) -> Normalized {
+        //split text into the letters as ints (position in the alphabet) and a
+        //template of where they came from, so case and punctuation can be
+        //restored
+        let mut letters = Vec::new();
+        let mut slots = Vec::new();
+        for c in s.chars() {
+            if let Some((i, lower)) = self.alphabet.lookup(c) {
+                letters.push(i);
+                slots.push(if lower || c.is_uppercase() || c.is_lowercase() {
+                    Slot::Letter(lower)
+                } else {
+                    Slot::Caseless
+                });
+                continue;
+            }
+            let upper = c.is_uppercase();
+            let folded = if self.fold_accents {
+                fold(c.to_lowercase().next().unwrap_or(c))
+            } else {
+                None
+            };
+            let found: Option<Vec<(u8, bool)>> = folded.and_then(|f| {
+                f.chars()
+                    .map(|l| {
+                        self.alphabet
+                            .lookup(if upper { l.to_ascii_uppercase() } else { l })
+                    })
+                    .collect()
+            });
+            match found {
+                Some(f) => {
+                    for (i, lower) in f {
+                        letters.push(i);
+                        slots.push(Slot::Letter(lower));
+                    }
+                }
+                None if self.keep_other => slots.push(Slot::Other(c)),
+                None => (),
+            }
+        }
+        Normalized {
+            letters,
+            slots,
+            alphabet: self.alphabet.clone(),
+        }
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Normalizer {
+        Normalizer::new()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Normalized {
+    pub letters: Vec<u8>,
+    slots: Vec<Slot>,
+    alphabet: Alphabet,
+}
+
+impl Normalized {
+    fn letter(&self, i: u8, lower: bool) -> char {
+        //only lower cased when that isn't a different letter of the alphabet
+        let c = self.alphabet.char_at(i);
+        let l = c.to_lowercase().next().unwrap_or(c);
+        if lower && self.alphabet.index(l).is_none() {
+            l
+        } else {
+            c
+        }
+    }
+
+    fn cases(&self) -> Vec<bool> {
+        //if each slot's letter should be lower case, a caseless slot takes the
+        //case of the nearest letters either side of it, lower unless neither
+        //of them is (so upper case text stays upper case)
+        let mut before = Vec::with_capacity(self.slots.len());
+        let mut case = None;
+        for slot in self.slots.iter() {
+            before.push(case);
+            if let Slot::Letter(lower) = *slot {
+                case = Some(lower);
+            }
+        }
+        let mut cases = vec![false; self.slots.len()];
+        case = None;
+        for (n, slot) in self.slots.iter().enumerate().rev() {
+            cases[n] = match *slot {
+                Slot::Letter(lower) => {
+                    case = Some(lower);
+                    lower
+                }
+                _ => before[n] == Some(true) || case == Some(true),
+            };
+        }
+        cases
+    }
+
+    pub fn restore(&self, letters: &[u8]) -> String {
+        //put (encoded or decoded) letters back in the original text, keeping
+        //the original case and punctuation. Any extra letters (eg hill
+        //padding) are added straight after the last letter, in it's case
+        let last = self
+            .slots
+            .iter()
+            .rposition(|s| !matches!(*s, Slot::Other(_)));
+        let cases = self.cases();
+        let mut out = String::new();
+        let mut i = 0;
+        for (n, slot) in self.slots.iter().enumerate() {
+            match *slot {
+                Slot::Letter(_) | Slot::Caseless => {
+                    let lower = cases[n];
+                    if i < letters.len() {
+                        out.push(self.letter(letters[i], lower));
+                        i += 1;
+                    }
+                    if Some(n) == last {
+                        for &l in letters[i..].iter() {
+                            out.push(self.letter(l, lower));
+                        }
+                        i = letters.len();
+                    }
+                }
+                Slot::Other(c) => out.push(c),
+            }
+        }
+        for &l in letters[i.min(letters.len())..].iter() {
+            out.push(self.letter(l, false));
+        }
+        out
+    }
+}
+
+pub fn clean(s: &str) -> Vec<u8> {
+    //just the letters of any text, as ints
+    Normalizer::strip().normalize(s).letters
+}
+
+pub fn clean_upper(s: &str) -> String {
+    //just the letters of any text, as an upper case string
+    int_to_str_lower(&clean(s)).to_ascii_uppercase()
+}
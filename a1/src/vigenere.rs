@@ -0,0 +1,198 @@
+use cipher::Cipher;
+use modular::modb_n;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sign {
+    //sign convention used to combine the key with the text
+    //Vigenere: c = p + k, Beaufort: c = k - p, VariantBeaufort: c = p - k
+    Vigenere,
+    Beaufort,
+    VariantBeaufort,
+}
+
+impl Sign {
+    fn enc(self, p: u8, k: u8, m: usize) -> u8 {
+        let (p, k) = (p as isize, k as isize);
+        modb_n(
+            match self {
+                Sign::Vigenere => p + k,
+                Sign::Beaufort => k - p,
+                Sign::VariantBeaufort => p - k,
+            },
+            m,
+        ) as u8
+    }
+
+    fn dec(self, c: u8, k: u8, m: usize) -> u8 {
+        let (c, k) = (c as isize, k as isize);
+        modb_n(
+            match self {
+                Sign::Vigenere => c - k,
+                Sign::Beaufort => k - c,
+                Sign::VariantBeaufort => c + k,
+            },
+            m,
+        ) as u8
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Vigenere {
+    //vigenere cipher (a=0, b=1...), with a repeating key or when autokey is
+    //set a 'moving' key, where the key is followed by the plaintext itself
+    //m is the modulus (size of the alphabet)
+    pub key: Vec<u8>,
+    pub sign: Sign,
+    pub autokey: bool,
+    pub m: usize,
+}
+
+impl Vigenere {
+    pub fn new(key: Vec<u8>) -> Option<Vigenere> {
+        Vigenere::with_sign(key, Sign::Vigenere)
+    }
+
+    pub fn with_sign(key: Vec<u8>, sign: Sign) -> Option<Vigenere> {
+        //the key needs at least one letter
+        if key.is_empty() {
+            return None;
+        }
+        Some(Vigenere {
+            key,
+            sign,
+            autokey: false,
+            m: 26,
+        })
+    }
+
+    pub fn autokey(key: Vec<u8>, sign: Sign) -> Option<Vigenere> {
+        Vigenere::with_sign(key, sign).map(|v| Vigenere { autokey: true, ..v })
+    }
+
+    pub fn with_modulus(self, m: usize) -> Option<Vigenere> {
+        //same cipher in a different sized alphabet (which can't be empty)
+        if m == 0 {
+            return None;
+        }
+        Some(Vigenere { m, ..self })
+    }
+
+    fn key_at(&self, i: usize, plain: &[u8]) -> u8 {
+        //key value for position i, plain only needs to be known up to i-1
+        let l = self.key.len();
+        if self.autokey && i >= l {
+            plain[i - l]
+        } else {
+            self.key[i % l]
+        }
+    }
+}
+
+impl Cipher for Vigenere {
+    fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        plain
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| self.sign.enc(p, self.key_at(i, plain), self.m))
+            .collect()
+    }
+    fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        //plaintext is built up as we go, as autokey needs it for the key
+        let mut plain = Vec::with_capacity(cipher.len());
+        for (i, &c) in cipher.iter().enumerate() {
+            let k = self.key_at(i, &plain);
+            plain.push(self.sign.dec(c, k, self.m));
+        }
+        plain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alphabet::Alphabet;
+    use text::{int_to_str_lower, str_upper_to_int};
+
+    fn enc(v: &Vigenere, plain: &str) -> String {
+        int_to_str_lower(&v.encrypt(&str_upper_to_int(plain)))
+    }
+
+    fn round_trip(v: &Vigenere) {
+        let plain = str_upper_to_int("DEFENDTHEEASTWALLOFTHECASTLE");
+        assert_eq!(v.decrypt(&v.encrypt(&plain)), plain);
+    }
+
+    #[test]
+    fn vigenere() {
+        let v = Vigenere::new(str_upper_to_int("LEMON")).unwrap();
+        assert_eq!(enc(&v, "ATTACKATDAWN"), "lxfopvefrnhr");
+        round_trip(&v);
+    }
+
+    #[test]
+    fn vigenere_autokey() {
+        let v = Vigenere::autokey(str_upper_to_int("QUEENLY"), Sign::Vigenere).unwrap();
+        assert_eq!(enc(&v, "ATTACKATDAWN"), "qnxepvytwtwp");
+        round_trip(&v);
+    }
+
+    #[test]
+    fn beaufort() {
+        let v = Vigenere::with_sign(str_upper_to_int("FORTIFICATION"), Sign::Beaufort).unwrap();
+        assert_eq!(
+            enc(&v, "DEFENDTHEEASTWALLOFTHECASTLE"),
+            "ckmpvcpvwpiwujogiuapvwriwuuk"
+        );
+        //beaufort is reciprocal, encrypting twice gives the plaintext back
+        let plain = str_upper_to_int("DEFENDTHEEASTWALLOFTHECASTLE");
+        assert_eq!(v.encrypt(&v.encrypt(&plain)), plain);
+        round_trip(&v);
+    }
+
+    #[test]
+    fn beaufort_autokey() {
+        round_trip(&Vigenere::autokey(str_upper_to_int("FORTIFICATION"), Sign::Beaufort).unwrap());
+    }
+
+    #[test]
+    fn variant_beaufort() {
+        //variant beaufort encryption is vigenere decryption
+        let key = str_upper_to_int("LEMON");
+        let v = Vigenere::with_sign(key.clone(), Sign::VariantBeaufort).unwrap();
+        let plain = str_upper_to_int("ATTACKATDAWN");
+        assert_eq!(
+            v.encrypt(&plain),
+            Vigenere::new(key).unwrap().decrypt(&plain)
+        );
+        round_trip(&v);
+    }
+
+    #[test]
+    fn variant_beaufort_autokey() {
+        round_trip(&Vigenere::autokey(str_upper_to_int("QUEENLY"), Sign::VariantBeaufort).unwrap());
+    }
+
+    #[test]
+    fn empty_key_or_modulus() {
+        assert!(Vigenere::new(Vec::new()).is_none());
+        assert!(Vigenere::with_sign(Vec::new(), Sign::Beaufort).is_none());
+        assert!(Vigenere::autokey(Vec::new(), Sign::Vigenere).is_none());
+        let v = Vigenere::new(str_upper_to_int("LEMON")).unwrap();
+        assert!(v.clone().with_modulus(0).is_none());
+        round_trip(&v.with_modulus(29).unwrap());
+    }
+
+    #[test]
+    fn caseless_letters() {
+        //in mod29 letters encrypt to space and punctuation (and back), which
+        //should come back in the case of the text around them
+        let alphabet = Alphabet::latin_punct();
+        for text in &["hello world, this is fine.", "HELLO WORLD, THIS IS FINE."] {
+            for k in 0..29 {
+                let v = Vigenere::new(vec![k]).unwrap().with_modulus(29).unwrap();
+                let cipher = v.encrypt_in(&alphabet, text);
+                assert_eq!(v.decrypt_in(&alphabet, &cipher), *text);
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fitness::Fitness;
+use text::int_to_str_lower;
+
+#[derive(Clone, Debug)]
+pub struct Wordlist {
+    //struct to hold set of english words, along with how often each word is
+    //used (1 if the list doesn't say)
+    counts: HashMap<String, u64>,
+    total: u64,
+    longest: usize,
+}
+
+impl Wordlist {
+    //unused word checker implemented, was going to be used for vigenere, maybe used in future asignments
+    /*fn check_n(&self, s: &String,from: usize,to:usize) -> Option<String> {
+       let mut s = s.clone();
+        for i in 0..to-from{
+            s.truncate(to-i);
+            if self.check(&s){
+                 return Some(s.clone());
+            }
+        }
+        None
+    }*/
+
+    pub fn check(&self, s: &str) -> bool {
+        //checks the wordlist for a string (word)
+        self.counts.contains_key(s)
+    }
+
+    pub fn coverage(&self, s: &str, min: usize, max: usize) -> usize {
+        //number of characters in s covered by words (min to max long), going
+        //left to right taking the longest word found at each point (in bytes,
+        //skipping any which would split a character)
+        let mut covered = 0;
+        let mut i = 0;
+        while i < s.len() {
+            let longest = (min..=max.min(s.len() - i)).rev().find(|&l| {
+                s.is_char_boundary(i) && s.is_char_boundary(i + l) && self.check(&s[i..i + l])
+            });
+            match longest {
+                Some(l) => {
+                    covered += l;
+                    i += l;
+                }
+                None => i += 1,
+            }
+        }
+        covered
+    }
+
+    pub fn probability(&self, word: &str) -> Option<f64> {
+        //chance of a word picked from (english) text being this one
+        self.counts.get(word).map(|&c| c as f64 / self.total as f64)
+    }
+
+    fn split(&self, s: &str) -> Vec<(usize, usize, bool)> {
+        //most likely way to split s into words, by dynamic programming over
+        //the log probability of the words. best[i] is the best score for the
+        //first i letters, letters which aren't part of any word are allowed
+        //but cost much more than the rarest word. Returns (start, end, known)
+        let n = s.len();
+        let unknown = (0.01 / self.total.max(1) as f64).log10();
+        let mut best: Vec<(f64, usize, bool)> = vec![(f64::NEG_INFINITY, 0, false); n + 1];
+        best[0].0 = 0f64;
+        for i in 1..=n {
+            best[i] = (best[i - 1].0 + unknown, i - 1, false);
+            for j in i.saturating_sub(self.longest)..i {
+                if !s.is_char_boundary(j) || !s.is_char_boundary(i) {
+                    continue;
+                }
+                if let Some(p) = self.probability(&s[j..i]) {
+                    let score = best[j].0 + p.log10();
+                    if score > best[i].0 {
+                        best[i] = (score, j, true);
+                    }
+                }
+            }
+        }
+        //walk back from the end, merging runs of unknown letters
+        let mut pieces: Vec<(usize, usize, bool)> = Vec::new();
+        let mut i = n;
+        while i > 0 {
+            let (_, j, known) = best[i];
+            match pieces.last_mut() {
+                Some(last) if !known && !last.2 => last.0 = j,
+                _ => pieces.push((j, i, known)),
+            }
+            i = j;
+        }
+        pieces.reverse();
+        pieces
+    }
+
+    pub fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        //split unspaced (lowercase) text into words, eg "itwasabright" into
+        //["it", "was", "a", "bright"]. Runs of letters that aren't words are
+        //kept together as one piece
+        self.split(s)
+            .into_iter()
+            .map(|(j, i, _)| &s[j..i])
+            .collect()
+    }
+
+    pub fn known_fraction(&self, s: &str) -> f64 {
+        //fraction of the letters in s that segment into dictionary words
+        if s.is_empty() {
+            return 0f64;
+        }
+        let known: usize = self
+            .split(s)
+            .into_iter()
+            .filter(|&(_, _, known)| known)
+            .map(|(j, i, _)| i - j)
+            .sum();
+        known as f64 / s.len() as f64
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn new() -> Wordlist {
+        //the bundled list of common english words (see data/english.words)
+        Wordlist::parse(include_str!("../data/english.words")).expect("bad bundled wordlist")
+    }
+
+    pub fn parse(s: &str) -> io::Result<Wordlist> {
+        //read a wordlist, one word per line optionally followed by how often
+        //it's used, # for comments. Also reads the front coded format of the
+        //bundled list, where '= <count>' gives the count of the words after it
+        //and words start with how many letters they share with the last one
+        let bad = |i: usize, msg: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", i + 1, msg),
+            )
+        };
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        let mut band: Option<u64> = None;
+        let mut last = String::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(c) = line.strip_prefix('=') {
+                band = Some(c.trim().parse().map_err(|_| bad(i, "bad count"))?);
+                last.clear();
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let mut word = parts.next().unwrap_or("").to_lowercase();
+            let count = match parts.next() {
+                Some(c) => c.parse().map_err(|_| bad(i, "bad count"))?,
+                None => band.unwrap_or(1),
+            };
+            if parts.next().is_some() {
+                return Err(bad(i, "expected '<word> [count]'"));
+            }
+            let shared = word.bytes().take_while(|c| c.is_ascii_digit()).count();
+            if band.is_some() && shared > 0 {
+                let n: usize = word[..shared].parse().map_err(|_| bad(i, "bad prefix"))?;
+                if n > last.len() || !last.is_char_boundary(n) {
+                    return Err(bad(i, "prefix longer than the last word"));
+                }
+                word = format!("{}{}", &last[..n], &word[shared..]);
+            }
+            *counts.entry(word.clone()).or_insert(0) += count;
+            last = word;
+        }
+        Ok(Wordlist {
+            total: counts.values().sum(),
+            longest: counts.keys().map(|w| w.len()).max().unwrap_or(0),
+            counts,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Wordlist> {
+        //read a wordlist from a file, eg
+        //https://github.com/dwyl/english-words/blob/master/words_alpha.txt
+        Wordlist::parse(&fs::read_to_string(path)?)
+    }
+}
+
+impl Default for Wordlist {
+    fn default() -> Wordlist {
+        Wordlist::new()
+    }
+}
+
+impl Fitness for Wordlist {
+    fn fitness(&self, text: &[u8]) -> f64 {
+        self.known_fraction(&int_to_str_lower(text))
+    }
+}
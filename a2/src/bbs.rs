@@ -0,0 +1,175 @@
+use std::fmt;
+
+use bigint::BigUint;
+
+pub trait RngCore {
+    //the same shape as rand's RngCore, for anything which makes a stream of
+    //random bytes
+    fn next_u32(&mut self) -> u32;
+    fn next_u64(&mut self) -> u64;
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BbsError {
+    NotPrime(BigUint),
+    NotThreeModFour(BigUint),
+    SamePrimes,
+    BadSeed,
+    TooManyBits(u32),
+}
+
+impl fmt::Display for BbsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BbsError::NotPrime(ref p) => write!(f, "{} isn't prime", p),
+            BbsError::NotThreeModFour(ref p) => write!(f, "{} isn't 3 mod 4", p),
+            BbsError::SamePrimes => write!(f, "p and q should be different primes"),
+            BbsError::BadSeed => write!(f, "the seed should be coprime to n = pq, and not 1"),
+            BbsError::TooManyBits(k) => write!(
+                f,
+                "at most {} bits a step are secure for this n (log2 log2 n)",
+                k
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BlumBlumShub {
+    //x(i+1) = x(i)^2 mod n, for n = pq with p and q both 3 mod 4, giving the
+    //low k bits of each x. The seed is x(0), so the first output is from
+    //x(1). lambda is the carmichael function of n, lcm(p-1, q-1), which every
+    //exponent can be taken mod to jump straight to x(i)
+    n: BigUint,
+    lambda: BigUint,
+    seed: BigUint,
+    state: BigUint,
+    k: u32,
+    //bits from the last step not handed out yet, the next one last
+    pending: Vec<u8>,
+}
+
+impl BlumBlumShub {
+    pub fn new(p: &BigUint, q: &BigUint, seed: &BigUint) -> Result<BlumBlumShub, BbsError> {
+        BlumBlumShub::with_bits(p, q, seed, 1)
+    }
+
+    pub fn with_bits(
+        p: &BigUint,
+        q: &BigUint,
+        seed: &BigUint,
+        k: u32,
+    ) -> Result<BlumBlumShub, BbsError> {
+        //k low bits of each step, up to log2(log2(n)) (at least 1)
+        let one = BigUint::from_u64(1);
+        for x in [p, q].iter() {
+            if !x.is_probable_prime() {
+                return Err(BbsError::NotPrime((*x).clone()));
+            }
+            if x.low_bits(2) != 3 {
+                return Err(BbsError::NotThreeModFour((*x).clone()));
+            }
+        }
+        if p == q {
+            return Err(BbsError::SamePrimes);
+        }
+        let n = p.mul(q);
+        let seed = seed.rem(&n).unwrap();
+        if seed.is_zero() || seed.is_one() || !seed.gcd(&n).is_one() {
+            return Err(BbsError::BadSeed);
+        }
+        //floor(log2(floor(log2(n))))
+        let max = (31 - (n.bits() - 1).leading_zeros()).max(1);
+        if k == 0 || k > max {
+            return Err(BbsError::TooManyBits(max));
+        }
+        let lambda = p.sub(&one).unwrap().lcm(&q.sub(&one).unwrap());
+        Ok(BlumBlumShub {
+            n,
+            lambda,
+            state: seed.clone(),
+            seed,
+            k,
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn from_u64(p: u64, q: u64, seed: u64) -> Result<BlumBlumShub, BbsError> {
+        BlumBlumShub::new(
+            &BigUint::from_u64(p),
+            &BigUint::from_u64(q),
+            &BigUint::from_u64(seed),
+        )
+    }
+
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
+
+    pub fn bits_per_step(&self) -> u32 {
+        self.k
+    }
+
+    pub fn next_state(&mut self) -> BigUint {
+        //square once, giving the new x (any bits left from the last step are
+        //dropped)
+        self.state = self.state.mul_mod(&self.state, &self.n).unwrap();
+        self.pending.clear();
+        self.state.clone()
+    }
+
+    pub fn nth_state(&self, i: u64) -> BigUint {
+        //x(i) = seed^(2^i) mod n = seed^(2^i mod lambda) mod n, without
+        //going through the i - 1 before it
+        let e = BigUint::from_u64(2)
+            .pow_mod(&BigUint::from_u64(i), &self.lambda)
+            .unwrap();
+        self.seed.pow_mod(&e, &self.n).unwrap()
+    }
+
+    pub fn seek(&mut self, i: u64) {
+        //jump so the next step gives x(i+1)
+        self.state = self.nth_state(i);
+        self.pending.clear();
+    }
+
+    pub fn next_bits(&mut self) -> u64 {
+        //the k low bits of the next x
+        self.next_state().low_bits(self.k)
+    }
+
+    pub fn next_bit(&mut self) -> u8 {
+        //one bit at a time, each step's k bits most significant first
+        if self.pending.is_empty() {
+            let x = self.next_bits();
+            self.pending = (0..self.k).map(|i| ((x >> i) & 1) as u8).collect();
+        }
+        self.pending.pop().unwrap()
+    }
+}
+
+impl RngCore for BlumBlumShub {
+    fn next_u32(&mut self) -> u32 {
+        (0..32).fold(0, |x, _| (x << 1) | self.next_bit() as u32)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        (0..64).fold(0, |x, _| (x << 1) | self.next_bit() as u64)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest.iter_mut() {
+            *b = (0..8).fold(0, |x, _| (x << 1) | self.next_bit());
+        }
+    }
+}
+
+impl Iterator for BlumBlumShub {
+    //the keystream one bit at a time (forever)
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        Some(self.next_bit())
+    }
+}
@@ -0,0 +1,357 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    //unsigned, any size. 32 bit limbs, least significant first, with no
+    //leading (high) zero limbs so zero is empty and equality is just ==
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn from_u64(x: u64) -> BigUint {
+        BigUint::from_limbs(vec![x as u32, (x >> 32) as u32])
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn parse(s: &str) -> Option<BigUint> {
+        //decimal digits only
+        let s = s.trim();
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut x = BigUint::zero();
+        for chunk in s.as_bytes().chunks(9) {
+            let digits = std::str::from_utf8(chunk).ok()?;
+            let scale = 10u32.pow(chunk.len() as u32);
+            x = x
+                .mul_small(scale)
+                .add(&BigUint::from_u64(digits.parse().ok()?));
+        }
+        Some(x)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u64),
+            2 => Some(self.limbs[0] as u64 | (self.limbs[1] as u64) << 32),
+            _ => None,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.limbs == [1]
+    }
+
+    pub fn bits(&self) -> u32 {
+        //number of bits needed, 0 for zero
+        match self.limbs.last() {
+            Some(&top) => 32 * self.limbs.len() as u32 - top.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, i: u32) -> bool {
+        self.limbs
+            .get((i / 32) as usize)
+            .is_some_and(|&l| (l >> (i % 32)) & 1 == 1)
+    }
+
+    pub fn low_bits(&self, k: u32) -> u64 {
+        //the k (up to 64) least significant bits
+        let low = self.limbs.first().cloned().unwrap_or(0) as u64
+            | (self.limbs.get(1).cloned().unwrap_or(0) as u64) << 32;
+        if k >= 64 {
+            low
+        } else {
+            low & ((1 << k) - 1)
+        }
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let n = self.limbs.len().max(other.limbs.len());
+        let mut out = Vec::with_capacity(n + 1);
+        let mut carry = 0u64;
+        for i in 0..n {
+            let t = self.limbs.get(i).cloned().unwrap_or(0) as u64
+                + other.limbs.get(i).cloned().unwrap_or(0) as u64
+                + carry;
+            out.push(t as u32);
+            carry = t >> 32;
+        }
+        out.push(carry as u32);
+        BigUint::from_limbs(out)
+    }
+
+    pub fn sub(&self, other: &BigUint) -> Option<BigUint> {
+        //None if other is bigger
+        if *self < *other {
+            return None;
+        }
+        let mut out = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &l) in self.limbs.iter().enumerate() {
+            let mut t = l as i64 - other.limbs.get(i).cloned().unwrap_or(0) as i64 - borrow;
+            borrow = 0;
+            if t < 0 {
+                t += 1 << 32;
+                borrow = 1;
+            }
+            out.push(t as u32);
+        }
+        Some(BigUint::from_limbs(out))
+    }
+
+    fn mul_small(&self, x: u32) -> BigUint {
+        let mut out = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u64;
+        for &l in self.limbs.iter() {
+            let t = l as u64 * x as u64 + carry;
+            out.push(t as u32);
+            carry = t >> 32;
+        }
+        out.push(carry as u32);
+        BigUint::from_limbs(out)
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        //schoolbook, fine for the few thousand bits used here
+        let mut out = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = a as u64 * b as u64 + out[i + j] as u64 + carry;
+                out[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            out[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(out)
+    }
+
+    pub fn shl(&self, k: u32) -> BigUint {
+        let (words, bits) = ((k / 32) as usize, k % 32);
+        let mut out = vec![0u32; words];
+        let mut carry = 0u32;
+        for &l in self.limbs.iter() {
+            if bits == 0 {
+                out.push(l);
+            } else {
+                out.push((l << bits) | carry);
+                carry = l >> (32 - bits);
+            }
+        }
+        out.push(carry);
+        BigUint::from_limbs(out)
+    }
+
+    pub fn shr(&self, k: u32) -> BigUint {
+        let (words, bits) = ((k / 32) as usize, k % 32);
+        if words >= self.limbs.len() {
+            return BigUint::zero();
+        }
+        let limbs = &self.limbs[words..];
+        let out = (0..limbs.len())
+            .map(|i| {
+                let high = limbs.get(i + 1).cloned().unwrap_or(0) as u64;
+                (((high << 32) | limbs[i] as u64) >> bits) as u32
+            })
+            .collect();
+        BigUint::from_limbs(out)
+    }
+
+    pub fn div_rem(&self, d: &BigUint) -> Option<(BigUint, BigUint)> {
+        //quotient and remainder, None dividing by zero. Long division a limb
+        //at a time (Knuth's algorithm D), guessing each quotient limb from
+        //the top two limbs and fixing it when it's one too big
+        if d.is_zero() {
+            return None;
+        }
+        if *self < *d {
+            return Some((BigUint::zero(), self.clone()));
+        }
+        if d.limbs.len() == 1 {
+            let d = d.limbs[0] as u64;
+            let mut q = vec![0u32; self.limbs.len()];
+            let mut r = 0u64;
+            for i in (0..self.limbs.len()).rev() {
+                let t = (r << 32) | self.limbs[i] as u64;
+                q[i] = (t / d) as u32;
+                r = t % d;
+            }
+            return Some((BigUint::from_limbs(q), BigUint::from_u64(r)));
+        }
+        //normalise so the top limb of the divisor has it's high bit set
+        let s = d.limbs.last().unwrap().leading_zeros();
+        let v = d.shl(s).limbs;
+        let mut u = self.shl(s).limbs;
+        let n = v.len();
+        if u.len() == self.limbs.len() {
+            u.push(0);
+        }
+        let m = u.len() - n - 1;
+        let b = 1u64 << 32;
+        let mut q = vec![0u32; m + 1];
+        for j in (0..=m).rev() {
+            let top = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+            let mut qhat = top / v[n - 1] as u64;
+            let mut rhat = top % v[n - 1] as u64;
+            while qhat >= b || qhat * v[n - 2] as u64 > ((rhat << 32) | u[j + n - 2] as u64) {
+                qhat -= 1;
+                rhat += v[n - 1] as u64;
+                if rhat >= b {
+                    break;
+                }
+            }
+            //u[j..j+n] -= qhat * v
+            let mut k = 0i64;
+            for i in 0..n {
+                let p = qhat * v[i] as u64;
+                let t = u[i + j] as i64 - k - (p & 0xffff_ffff) as i64;
+                u[i + j] = t as u32;
+                k = (p >> 32) as i64 - (t >> 32);
+            }
+            let t = u[j + n] as i64 - k;
+            u[j + n] = t as u32;
+            if t < 0 {
+                //qhat was one too many, add v back
+                qhat -= 1;
+                let mut carry = 0u64;
+                for i in 0..n {
+                    let t = u[i + j] as u64 + v[i] as u64 + carry;
+                    u[i + j] = t as u32;
+                    carry = t >> 32;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+            q[j] = qhat as u32;
+        }
+        u.truncate(n);
+        Some((BigUint::from_limbs(q), BigUint::from_limbs(u).shr(s)))
+    }
+
+    pub fn rem(&self, d: &BigUint) -> Option<BigUint> {
+        self.div_rem(d).map(|(_, r)| r)
+    }
+
+    pub fn mul_mod(&self, other: &BigUint, n: &BigUint) -> Option<BigUint> {
+        self.mul(other).rem(n)
+    }
+
+    pub fn pow_mod(&self, e: &BigUint, n: &BigUint) -> Option<BigUint> {
+        //self^e mod n, square and multiply from the top bit of e
+        let base = self.rem(n)?;
+        let mut r = BigUint::from_u64(1).rem(n)?;
+        for i in (0..e.bits()).rev() {
+            r = r.mul_mod(&r, n)?;
+            if e.bit(i) {
+                r = r.mul_mod(&base, n)?;
+            }
+        }
+        Some(r)
+    }
+
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b).unwrap();
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    pub fn lcm(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        self.div_rem(&self.gcd(other)).unwrap().0.mul(other)
+    }
+
+    pub fn is_probable_prime(&self) -> bool {
+        //miller rabin with the first 20 primes as bases. Exact below about
+        //3*10^24, and a composite passing all of them is vanishingly rare
+        let bases = [
+            2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+        ];
+        if let Some(x) = self.to_u64() {
+            if x < 2 {
+                return false;
+            }
+            if let Some(&p) = bases.iter().find(|&&p| x % p == 0) {
+                return x == p;
+            }
+        } else if bases
+            .iter()
+            .any(|&p| self.rem(&BigUint::from_u64(p)).unwrap().is_zero())
+        {
+            return false;
+        }
+        let one = BigUint::from_u64(1);
+        let n1 = self.sub(&one).unwrap();
+        let s = (0..).find(|&i| n1.bit(i)).unwrap();
+        let d = n1.shr(s);
+        bases.iter().all(|&a| {
+            let mut x = BigUint::from_u64(a).pow_mod(&d, self).unwrap();
+            if x == one || x == n1 {
+                return true;
+            }
+            (1..s).any(|_| {
+                x = x.mul_mod(&x, self).unwrap();
+                x == n1
+            })
+        })
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        //9 decimal digits at a time, from the bottom
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let billion = BigUint::from_u64(1_000_000_000);
+        let mut chunks = Vec::new();
+        let mut x = self.clone();
+        while !x.is_zero() {
+            let (q, r) = x.div_rem(&billion).unwrap();
+            chunks.push(r.to_u64().unwrap());
+            x = q;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for c in chunks.iter().rev() {
+            write!(f, "{:09}", c)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+
+use a2::{berlekamp_massey, recover_lfsr, solve_lfsr, BigUint, BlumBlumShub, Lfsr};
+
+pub const USAGE: &str = "usage:
+    a2                                      run the assignment questions
+    a2 encrypt <cipher> <key> [file]        encrypt a file (or stdin) of bits
+    a2 decrypt <cipher> <key> [file]        decrypt a file (or stdin) of bits
+    a2 crack <cipher> [file]                recover the key and decrypt
+    a2 recover [file]                       shortest lfsr making a keystream
+
+ciphers and keys:
+    lfsr                101101/101110 (taps c0..cm-1 / starting state)
+    bbs                 7,19,2 (p,q,seed, both primes 3 mod 4, any size)
+                        or 7,19,2,2 for the 2 low bits of each step
+
+options:
+    --json              print json instead of text
+    --plain <bits>      (crack lfsr) known plaintext for the start of the text,
+                        at least 2m bits
+    --size <m>          (crack lfsr) length of the lfsr, default 6";
+
+enum Json {
+    //just enough json for printing results
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            //NaN and inf aren't numbers as far as json is concerned
+            Json::Num(n) if !n.is_finite() => write!(f, "null"),
+            Json::Num(n) => write!(f, "{}", n),
+            Json::Str(ref s) => write!(f, "{}", escape(s)),
+        }
+    }
+}
+
+fn ints_to_str(v: &[u8]) -> String {
+    v.iter().map(|i| i.to_string()).collect()
+}
+
+fn render(fields: Vec<(&str, Json)>, json: bool) -> String {
+    //the fields as a json object, or one 'name: value' line each
+    if json {
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(k, v)| format!("{}:{}", escape(k), v))
+            .collect();
+        return format!("{{{}}}", body.join(","));
+    }
+    fields
+        .into_iter()
+        .map(|(k, v)| match v {
+            Json::Str(s) => format!("{}: {}", k, s),
+            v => format!("{}: {}", k, v),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+struct Options {
+    //parsed command line, anything not an option is positional
+    json: bool,
+    plain: Option<String>,
+    size: usize,
+    positional: Vec<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut opts = Options {
+        json: false,
+        plain: None,
+        size: 6,
+        positional: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--plain" => opts.plain = Some(value("--plain")?),
+            "--size" => {
+                opts.size = value("--size")?
+                    .parse()
+                    .map_err(|_| "--size should be a number".to_owned())?
+            }
+            a if a.starts_with("--") => return Err(format!("unknown option {}", a)),
+            _ => opts.positional.push(arg.clone()),
+        }
+    }
+    Ok(opts)
+}
+
+fn read_input(path: Option<&String>) -> Result<String, String> {
+    //read from the file given, or stdin when there isn't one (or it's -)
+    match path.map(|p| p.as_str()) {
+        None | Some("-") => {
+            let mut s = String::new();
+            io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("couldn't read stdin: {}", e))?;
+            Ok(s)
+        }
+        Some(p) => fs::read_to_string(p).map_err(|e| format!("couldn't read {}: {}", p, e)),
+    }
+}
+
+fn bits(s: &str) -> Result<Vec<u8>, String> {
+    //a string of 0s and 1s (whitespace is ignored) as ints
+    s.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '0' => Ok(0),
+            '1' => Ok(1),
+            _ => Err(format!("'{}' isn't a bit", c)),
+        })
+        .collect()
+}
+
+fn keystream(name: &str, key: &str, n: usize) -> Result<Vec<u8>, String> {
+    //n bits of keystream for a cipher name and key from the command line
+    match name {
+        "lfsr" => {
+            let mut parts = key.split('/');
+            let lfsr = match (parts.next(), parts.next(), parts.next()) {
+                (Some(c), Some(s), None) => Lfsr::fibonacci(bits(c)?, bits(s)?),
+                _ => return Err("lfsr keys are taps/state, eg 101101/101110".to_owned()),
+            };
+            let lfsr = lfsr.ok_or("lfsr taps and state should be the same length")?;
+            Ok(lfsr.take(n).collect())
+        }
+        "bbs" => {
+            let v: Vec<BigUint> = key
+                .split(',')
+                .map(|x| BigUint::parse(x).ok_or_else(|| format!("'{}' isn't a number", x)))
+                .collect::<Result<Vec<BigUint>, String>>()?;
+            let bbs = match v.as_slice() {
+                [p, q, s] => BlumBlumShub::new(p, q, s),
+                [p, q, s, k] => {
+                    let k = k.to_u64().unwrap_or(u64::MAX).min(u32::MAX as u64) as u32;
+                    BlumBlumShub::with_bits(p, q, s, k)
+                }
+                _ => return Err("bbs keys are p,q,seed or p,q,seed,bits".to_owned()),
+            };
+            let bbs = bbs.map_err(|e| e.to_string())?;
+            Ok(bbs.take(n).collect())
+        }
+        _ => Err(format!("unknown cipher {}", name)),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    //run a command line (without the program name), giving what to print
+    let command = args.first().map(|c| c.as_str()).unwrap_or("help");
+    let opts = parse_options(&args[1.min(args.len())..])?;
+    let pos = &opts.positional;
+    match command {
+        "encrypt" | "decrypt" => {
+            //both are xor with the keystream
+            if pos.len() < 2 || pos.len() > 3 {
+                return Err(format!("{} needs a cipher and a key", command));
+            }
+            let text = bits(&read_input(pos.get(2))?)?;
+            let key = keystream(&pos[0], &pos[1], text.len())?;
+            let out: Vec<u8> = text.iter().zip(key.iter()).map(|(t, k)| t ^ k).collect();
+            let field = if command == "encrypt" {
+                "ciphertext"
+            } else {
+                "plaintext"
+            };
+            if opts.json {
+                Ok(render(
+                    vec![
+                        ("cipher", Json::Str(pos[0].clone())),
+                        ("key", Json::Str(pos[1].clone())),
+                        (field, Json::Str(ints_to_str(&out))),
+                    ],
+                    true,
+                ))
+            } else {
+                Ok(ints_to_str(&out))
+            }
+        }
+        "crack" => {
+            if pos.is_empty() || pos.len() > 2 {
+                return Err("crack needs a cipher".to_owned());
+            }
+            if pos[0] != "lfsr" {
+                return Err(format!("can't crack {}", pos[0]));
+            }
+            let plain = opts
+                .plain
+                .as_ref()
+                .ok_or("cracking lfsr needs known plaintext (--plain)")?;
+            let plain = bits(plain)?;
+            let cipher = bits(&read_input(pos.get(1))?)?;
+            if opts.size == 0 {
+                return Err("--size should be at least 1".to_owned());
+            }
+            if cipher.len() < plain.len() {
+                return Err("the ciphertext is shorter than the known plaintext".to_owned());
+            }
+            let report = solve_lfsr(&plain, &cipher, opts.size).ok_or_else(|| {
+                format!(
+                    "need at least {} bits of known plaintext and ciphertext",
+                    2 * opts.size
+                )
+            })?;
+            if opts.json {
+                let (taps, decrypted) = match (report.taps, report.decrypted) {
+                    (Some(taps), Some(decrypted)) => (taps, decrypted),
+                    _ => {
+                        return Err(
+                            "the keystream matrix is singular, try a smaller --size".to_owned()
+                        )
+                    }
+                };
+                Ok(render(
+                    vec![
+                        ("cipher", Json::Str(pos[0].clone())),
+                        ("size", Json::Num(opts.size as f64)),
+                        ("keystream", Json::Str(ints_to_str(&report.keystream))),
+                        ("taps", Json::Str(ints_to_str(&taps))),
+                        ("plaintext", Json::Str(ints_to_str(&decrypted))),
+                    ],
+                    true,
+                ))
+            } else {
+                Ok(report.to_string())
+            }
+        }
+        "recover" => {
+            if pos.len() > 1 {
+                return Err("recover takes at most one file".to_owned());
+            }
+            let keystream = bits(&read_input(pos.first())?)?;
+            let (_, complexity) = berlekamp_massey(&keystream);
+            let lfsr = recover_lfsr(&keystream).ok_or("the keystream is all zeros")?;
+            Ok(render(
+                vec![
+                    ("complexity", Json::Num(complexity as f64)),
+                    ("taps", Json::Str(ints_to_str(&lfsr.taps))),
+                    ("state", Json::Str(ints_to_str(&lfsr.state))),
+                    (
+                        "period",
+                        lfsr.period().map_or(Json::Null, |p| Json::Num(p as f64)),
+                    ),
+                    ("maximal", Json::Bool(lfsr.is_maximal())),
+                ],
+                opts.json,
+            ))
+        }
+        "help" | "--help" | "-h" => Ok(USAGE.to_owned()),
+        _ => Err(format!("unknown command {}", command)),
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+
+//Bits packed 64 to a word, adding is xor and multiplying is and, so a row
+//operation is a word at a time. Bits past the length are always kept 0
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitVec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    pub fn new(len: usize) -> BitVec {
+        //all zeros
+        BitVec {
+            len,
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn from_bits(bits: &[u8]) -> BitVec {
+        //from a slice of 0s and 1s (anything odd counts as 1)
+        let mut v = BitVec::new(bits.len());
+        for (i, &b) in bits.iter().enumerate() {
+            if b & 1 == 1 {
+                v.set(i, true);
+            }
+        }
+        v
+    }
+
+    pub fn to_bits(&self) -> Vec<u8> {
+        (0..self.len).map(|i| self.get(i) as u8).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        if bit {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    pub fn xor_with(&mut self, other: &BitVec) {
+        for (w, o) in self.words.iter_mut().zip(other.words.iter()) {
+            *w ^= o;
+        }
+    }
+
+    pub fn dot(&self, other: &BitVec) -> bool {
+        //sum of the products, the parity of the bits in common
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .fold(0, |x, (a, b)| x ^ (a & b).count_ones())
+            & 1
+            == 1
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+}
+
+impl fmt::Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.len {
+            write!(f, "{}", self.get(i) as u8)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitMatrix {
+    //rows x cols, each row a BitVec
+    cols: usize,
+    rows: Vec<BitVec>,
+}
+
+impl BitMatrix {
+    pub fn new(rows: usize, cols: usize) -> BitMatrix {
+        BitMatrix {
+            cols,
+            rows: vec![BitVec::new(cols); rows],
+        }
+    }
+
+    pub fn identity(n: usize) -> BitMatrix {
+        let mut m = BitMatrix::new(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    pub fn from_rows(rows: Vec<BitVec>) -> Option<BitMatrix> {
+        //None if the rows aren't all the same length
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return None;
+        }
+        Some(BitMatrix { cols, rows })
+    }
+
+    pub fn from_bits(rows: &[Vec<u8>]) -> Option<BitMatrix> {
+        BitMatrix::from_rows(rows.iter().map(|r| BitVec::from_bits(r)).collect())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &BitVec {
+        &self.rows[i]
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> bool {
+        self.rows[r].get(c)
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, bit: bool) {
+        self.rows[r].set(c, bit)
+    }
+
+    pub fn transpose(&self) -> BitMatrix {
+        let mut t = BitMatrix::new(self.cols, self.rows());
+        for (r, row) in self.rows.iter().enumerate() {
+            for c in (0..self.cols).filter(|&c| row.get(c)) {
+                t.set(c, r, true);
+            }
+        }
+        t
+    }
+
+    pub fn mul_vec(&self, v: &BitVec) -> Option<BitVec> {
+        //self * v, with v as a column. None if the sizes don't fit
+        if v.len() != self.cols {
+            return None;
+        }
+        let mut out = BitVec::new(self.rows());
+        for (i, row) in self.rows.iter().enumerate() {
+            out.set(i, row.dot(v));
+        }
+        Some(out)
+    }
+
+    pub fn mul(&self, other: &BitMatrix) -> Option<BitMatrix> {
+        //self * other, each row of the result is the xor of the rows of other
+        //picked out by the same row of self
+        if self.cols != other.rows() {
+            return None;
+        }
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut out = BitVec::new(other.cols);
+                for j in (0..self.cols).filter(|&j| row.get(j)) {
+                    out.xor_with(&other.rows[j]);
+                }
+                out
+            })
+            .collect();
+        Some(BitMatrix {
+            cols: other.cols,
+            rows,
+        })
+    }
+
+    fn augment(&self, other: &BitMatrix) -> BitMatrix {
+        //[self | other], same number of rows
+        let mut m = BitMatrix::new(self.rows(), self.cols + other.cols);
+        for r in 0..self.rows() {
+            for c in (0..self.cols).filter(|&c| self.get(r, c)) {
+                m.set(r, c, true);
+            }
+            for c in (0..other.cols).filter(|&c| other.get(r, c)) {
+                m.set(r, self.cols + c, true);
+            }
+        }
+        m
+    }
+
+    fn reduce(&mut self, limit: usize) -> Vec<usize> {
+        //gaussian elimination to reduced row echelon form, only pivoting in
+        //the first limit columns. Gives the pivot column of each pivot row
+        //(which are the first rows)
+        let mut pivots = Vec::new();
+        for col in 0..limit.min(self.cols) {
+            let top = pivots.len();
+            let pivot = match (top..self.rows()).find(|&r| self.rows[r].get(col)) {
+                Some(p) => p,
+                None => continue,
+            };
+            self.rows.swap(top, pivot);
+            let pivot_row = self.rows[top].clone();
+            for (r, row) in self.rows.iter_mut().enumerate() {
+                if r != top && row.get(col) {
+                    row.xor_with(&pivot_row);
+                }
+            }
+            pivots.push(col);
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().reduce(self.cols).len()
+    }
+
+    pub fn inverse(&self) -> Option<BitMatrix> {
+        //reducing [self | I] gives [I | self^-1], None if it's singular
+        let n = self.rows();
+        if self.cols != n {
+            return None;
+        }
+        let mut m = self.augment(&BitMatrix::identity(n));
+        if m.reduce(n).len() != n {
+            return None;
+        }
+        let mut inv = BitMatrix::new(n, n);
+        for r in 0..n {
+            for c in (0..n).filter(|&c| m.get(r, n + c)) {
+                inv.set(r, c, true);
+            }
+        }
+        Some(inv)
+    }
+
+    pub fn kernel(&self) -> Vec<BitVec> {
+        //a basis for every x with self * x = 0, one per free column: that
+        //column set, and each pivot column set to cancel it out
+        let mut m = self.clone();
+        let pivots = m.reduce(self.cols);
+        (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut x = BitVec::new(self.cols);
+                x.set(free, true);
+                for (r, &p) in pivots.iter().enumerate() {
+                    x.set(p, m.get(r, free));
+                }
+                x
+            })
+            .collect()
+    }
+
+    pub fn solve(&self, b: &BitVec) -> Option<BitVec> {
+        //an x with self * x = b (free variables 0), None if there isn't one.
+        //It's the only one when the rank is the number of columns
+        if b.len() != self.rows() {
+            return None;
+        }
+        let column = BitMatrix::from_rows(
+            b.to_bits()
+                .iter()
+                .map(|&y| BitVec::from_bits(&[y]))
+                .collect(),
+        )?;
+        let mut m = self.augment(&column);
+        let pivots = m.reduce(self.cols + 1);
+        if pivots.last() == Some(&self.cols) {
+            //0 = 1 somewhere, no solution
+            return None;
+        }
+        let mut x = BitVec::new(self.cols);
+        for (r, &p) in pivots.iter().enumerate() {
+            x.set(p, m.get(r, self.cols));
+        }
+        Some(x)
+    }
+}
+
+impl fmt::Display for BitMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        //a row of 0s and 1s per line
+        let rows: Vec<String> = self.rows.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
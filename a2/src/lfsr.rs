@@ -0,0 +1,307 @@
+use std::fmt;
+
+use gf2::{BitMatrix, BitVec};
+use poly;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Form {
+    //Fibonacci xors the tapped bits into the one shifted in, Galois xors the
+    //bit shifted out into the tapped bits. Both give a sequence with the same
+    //recurrence, but from different states
+    Fibonacci,
+    Galois,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lfsr {
+    //taps are c0..cm-1 for z(i+m) = c0*z(i) + c1*z(i+1) + ... + cm-1*z(i+m-1)
+    //(mod 2), the state is m bits and the next output is state[0]. In
+    //Fibonacci form the state is just the next m outputs
+    pub taps: Vec<u8>,
+    pub state: Vec<u8>,
+    pub form: Form,
+}
+
+impl Lfsr {
+    pub fn new(taps: Vec<u8>, state: Vec<u8>, form: Form) -> Option<Lfsr> {
+        //taps and state need to be the same (non zero) length, and just bits
+        if taps.is_empty()
+            || taps.len() != state.len()
+            || taps.iter().chain(state.iter()).any(|&b| b > 1)
+        {
+            return None;
+        }
+        Some(Lfsr { taps, state, form })
+    }
+
+    pub fn fibonacci(taps: Vec<u8>, state: Vec<u8>) -> Option<Lfsr> {
+        Lfsr::new(taps, state, Form::Fibonacci)
+    }
+
+    pub fn galois(taps: Vec<u8>, state: Vec<u8>) -> Option<Lfsr> {
+        Lfsr::new(taps, state, Form::Galois)
+    }
+
+    pub fn len(&self) -> usize {
+        self.taps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.taps.is_empty()
+    }
+
+    pub fn next_bit(&mut self) -> u8 {
+        let m = self.len();
+        let out = self.state[0];
+        match self.form {
+            Form::Fibonacci => {
+                let feedback = self
+                    .taps
+                    .iter()
+                    .zip(self.state.iter())
+                    .fold(0, |x, (&c, &s)| x ^ (c & s));
+                self.state.remove(0);
+                self.state.push(feedback);
+            }
+            Form::Galois => {
+                //the taps go in backwards, so the state steps like the
+                //companion matrix of the same polynomial
+                for j in 0..m - 1 {
+                    self.state[j] = self.state[j + 1] ^ (out & self.taps[m - 1 - j]);
+                }
+                self.state[m - 1] = out & self.taps[0];
+            }
+        }
+        out
+    }
+
+    pub fn connection_polynomial(&self) -> Option<u128> {
+        //x^m + cm-1 x^(m-1) + ... + c1 x + c0, the same for both forms. None
+        //past 64 taps, which is as big as poly handles
+        if self.len() > 64 {
+            return None;
+        }
+        Some(
+            self.taps
+                .iter()
+                .enumerate()
+                .fold(1u128 << self.len(), |f, (j, &c)| f | ((c as u128) << j)),
+        )
+    }
+
+    pub fn is_maximal(&self) -> bool {
+        //whether every non zero state gives a sequence of period 2^m - 1
+        self.connection_polynomial().is_some_and(poly::is_primitive)
+    }
+
+    pub fn period(&self) -> Option<u128> {
+        //Exact period of the output from this state, the order of x mod the
+        //minimal polynomial of the sequence (found by Berlekamp Massey from
+        //2m bits), which divides the connection polynomial. None if the
+        //output never repeats from it's start (c0 is 0) or it's over 64 taps
+        let m = self.len();
+        if m > 64 {
+            return None;
+        }
+        let bits: Vec<u8> = self.clone().take(2 * m).collect();
+        let (taps, l) = berlekamp_massey(&bits);
+        if l == 0 {
+            //all zeros
+            return Some(1);
+        }
+        let f = taps
+            .iter()
+            .enumerate()
+            .fold(1u128 << l, |f, (j, &c)| f | ((c as u128) << j));
+        poly::order(f)
+    }
+
+    pub fn bytes(&mut self) -> Bytes<'_> {
+        Bytes { lfsr: self }
+    }
+
+    pub fn xor_bits(&mut self, bits: &[u8]) -> Vec<u8> {
+        //stream encryption (or decryption) of a slice of bits
+        bits.iter().map(|&b| b ^ self.next_bit()).collect()
+    }
+
+    pub fn xor_bytes(&mut self, data: &[u8]) -> Vec<u8> {
+        //same, 8 bits of keystream per byte
+        data.iter().zip(self.bytes()).map(|(&d, k)| d ^ k).collect()
+    }
+}
+
+impl Iterator for Lfsr {
+    //the keystream, one bit at a time (forever)
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        Some(self.next_bit())
+    }
+}
+
+pub struct Bytes<'a> {
+    //the keystream 8 bits at a time, first bit as the most significant
+    lfsr: &'a mut Lfsr,
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        Some((0..8).fold(0u8, |x, _| (x << 1) | self.lfsr.next_bit()))
+    }
+}
+
+impl fmt::Display for Lfsr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bits = |v: &[u8]| v.iter().map(|b| b.to_string()).collect::<String>();
+        write!(
+            f,
+            "{:?} LFSR, taps {} state {}",
+            self.form,
+            bits(&self.taps),
+            bits(&self.state)
+        )
+    }
+}
+
+pub fn berlekamp_massey(s: &[u8]) -> (Vec<u8>, usize) {
+    //Shortest LFSR which makes the sequence, as it's taps (in the same order
+    //as Lfsr) and length, the linear complexity. Only unique if the sequence
+    //is at least twice the complexity.
+    //c is the connection polynomial 1 + c1x + ... + cLx^L where
+    //s(n) = c1*s(n-1) + ... + cL*s(n-L), b is it from before the last length
+    //change, which is added back in (shifted) when a discrepancy shows up
+    let mut c = vec![1u8];
+    let mut b = vec![1u8];
+    let mut l = 0;
+    let mut shift = 1;
+    for n in 0..s.len() {
+        let d = (1..=l).fold(s[n], |d, i| d ^ (c.get(i).cloned().unwrap_or(0) & s[n - i]));
+        if d == 0 {
+            shift += 1;
+            continue;
+        }
+        let old = c.clone();
+        if c.len() < b.len() + shift {
+            c.resize(b.len() + shift, 0);
+        }
+        for (i, &x) in b.iter().enumerate() {
+            c[i + shift] ^= x;
+        }
+        if 2 * l <= n {
+            l = n + 1 - l;
+            b = old;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    c.resize(l + 1, 0);
+    //taps[j] multiplies z(i+j), which is s(n-(L-j))
+    let taps = (0..l).map(|j| c[l - j]).collect();
+    (taps, l)
+}
+
+pub fn recover_lfsr(s: &[u8]) -> Option<Lfsr> {
+    //the shortest Fibonacci LFSR giving a keystream, started at the start of
+    //it. None if the keystream is all zeros (there's no register to find)
+    let (taps, l) = berlekamp_massey(s);
+    Lfsr::fibonacci(taps, s.get(..l)?.to_vec())
+}
+
+fn bits_str(v: &[u8]) -> String {
+    v.iter().map(|b| b.to_string()).collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct LfsrReport {
+    //keystream found from P xor C, with the m x m matrix of keystream bits
+    //and Lm+1 - L2m, solving them for the taps c0..cm-1 (None when the
+    //matrix is singular). With the taps the whole ciphertext is decrypted
+    pub plaintext: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub keystream: Vec<u8>,
+    pub matrix: BitMatrix,
+    pub input: Vec<u8>,
+    pub taps: Option<Vec<u8>>,
+    pub decrypted: Option<Vec<u8>>,
+}
+
+pub fn solve_lfsr(plain: &[u8], cipher: &[u8], m: usize) -> Option<LfsrReport> {
+    //known plaintext attack on an m bit LFSR, from bits of plaintext and the
+    //ciphertext starting at the same place (which can go on past it). None
+    //if m is 0 or there aren't 2m bits of both to find the keystream from
+    //XOR'ing P and C for L
+    let keystream: Vec<u8> = plain
+        .iter()
+        .zip(cipher.iter())
+        .map(|(p, c)| (p ^ c) & 1)
+        .collect();
+    if m == 0 || keystream.len() < 2 * m {
+        return None;
+    }
+    let rows = (0..m)
+        .map(|i| BitVec::from_bits(&keystream[i..(i + m)]))
+        .collect();
+    let matrix = BitMatrix::from_rows(rows)?;
+    let input = keystream[m..(2 * m)].to_vec();
+    //only a full rank matrix gives one answer
+    let taps = if matrix.rank() == m {
+        matrix
+            .solve(&BitVec::from_bits(&input))
+            .map(|c| c.to_bits())
+    } else {
+        None
+    };
+    //the keystream continues from it's start with the taps found
+    let decrypted = taps.as_ref().and_then(|taps| {
+        let mut lfsr = Lfsr::fibonacci(taps.clone(), keystream[..m].to_vec())?;
+        Some(lfsr.xor_bits(cipher))
+    });
+    Some(LfsrReport {
+        plaintext: plain.to_vec(),
+        ciphertext: cipher.to_vec(),
+        keystream,
+        matrix,
+        input,
+        taps,
+        decrypted,
+    })
+}
+
+impl fmt::Display for LfsrReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Calculate l (keystream);")?;
+        writeln!(f, "XOR'ing P and C for L")?;
+        writeln!(
+            f,
+            "P: {}\nC: {}\nL: {}",
+            bits_str(&self.plaintext),
+            bits_str(&self.ciphertext),
+            bits_str(&self.keystream)
+        )?;
+        writeln!(f, "Create Matrix of;")?;
+        writeln!(f, "|L1 L2 ... Lm-1 Lm  |")?;
+        writeln!(f, "|L2 L3 ... Lm-2 Lm-1|")?;
+        for _ in 0..3 {
+            writeln!(f, "|.                 .|")?;
+        }
+        writeln!(f, "|Lm Lm+1 ...    Lm+m|")?;
+        writeln!(f, "\n{}", self.matrix)?;
+        writeln!(f, "Lm+1 - L2m: |{}|", bits_str(&self.input))?;
+        writeln!(
+            f,
+            "Solving Matrix * |c0 ... cm-1| = |Lm+1 ... L2m| over GF(2);"
+        )?;
+        match (&self.taps, &self.decrypted) {
+            (Some(taps), Some(plain)) => {
+                writeln!(f, "c: {}", bits_str(taps))?;
+                writeln!(f, "Regenerating the keystream to decrypt C;")?;
+                writeln!(f, "P: {}", bits_str(plain))
+            }
+            _ => writeln!(f, "Matrix is singular, no unique taps (try a smaller m)"),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod bbs;
+pub mod bigint;
+pub mod gf2;
+pub mod lfsr;
+pub mod poly;
+
+pub use bbs::{BbsError, BlumBlumShub, RngCore};
+pub use bigint::BigUint;
+pub use lfsr::{berlekamp_massey, recover_lfsr, solve_lfsr, Form, Lfsr, LfsrReport};
@@ -0,0 +1,319 @@
+//Polynomials over GF(2) packed into a u128, bit i is the coefficient of x^i.
+//Anything taken mod f needs f to be degree 64 or less, so products fit
+
+pub fn degree(a: u128) -> Option<u32> {
+    //None for the zero polynomial
+    if a == 0 {
+        None
+    } else {
+        Some(127 - a.leading_zeros())
+    }
+}
+
+pub fn div_rem(a: u128, b: u128) -> (u128, u128) {
+    let db = degree(b).expect("dividing by the zero polynomial");
+    let mut q = 0;
+    let mut r = a;
+    while let Some(dr) = degree(r) {
+        if dr < db {
+            break;
+        }
+        q |= 1 << (dr - db);
+        r ^= b << (dr - db);
+    }
+    (q, r)
+}
+
+pub fn gcd(a: u128, b: u128) -> u128 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        let r = div_rem(a, b).1;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+pub fn mul_mod(a: u128, b: u128, f: u128) -> u128 {
+    //shift and add, reducing a each time it reaches the degree of f
+    let df = degree(f).expect("mod the zero polynomial");
+    let mut a = div_rem(a, f).1;
+    let mut b = b;
+    let mut r = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            r ^= a;
+        }
+        b >>= 1;
+        a <<= 1;
+        if (a >> df) & 1 == 1 {
+            a ^= f;
+        }
+    }
+    div_rem(r, f).1
+}
+
+pub fn pow_mod(a: u128, e: u128, f: u128) -> u128 {
+    let mut base = div_rem(a, f).1;
+    let mut e = e;
+    let mut r = div_rem(1, f).1;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = mul_mod(r, base, f);
+        }
+        base = mul_mod(base, base, f);
+        e >>= 1;
+    }
+    r
+}
+
+fn derivative(a: u128) -> u128 {
+    //d/dx x^i = i x^(i-1), which is 0 for even i in GF(2)
+    (a >> 1) & 0x5555_5555_5555_5555_5555_5555_5555_5555
+}
+
+fn sqrt(a: u128) -> u128 {
+    //a polynomial with a zero derivative is a square, g(x)^2 = g(x^2)
+    (0..64)
+        .filter(|i| (a >> (2 * i)) & 1 == 1)
+        .fold(0, |g, i| g | (1 << i))
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn split_equal_degree(f: u128, d: u32, rng: &mut XorShift, out: &mut Vec<u128>) {
+    //f is a product of distinct irreducibles all of degree d (Cantor
+    //Zassenhaus). The trace a + a^2 + ... + a^(2^(d-1)) of a random a is 0 or
+    //1 mod each factor, at random, so gcd with f splits it about half the time
+    let df = degree(f).unwrap_or(0);
+    if df <= d {
+        if df > 0 {
+            out.push(f);
+        }
+        return;
+    }
+    loop {
+        let a = div_rem((rng.next() as u128) | ((rng.next() as u128) << 64), f).1;
+        let mut t = a;
+        let mut sq = a;
+        for _ in 1..d {
+            sq = mul_mod(sq, sq, f);
+            t ^= sq;
+        }
+        let g = gcd(f, t);
+        if degree(g).unwrap_or(0) > 0 && g != f {
+            split_equal_degree(g, d, rng, out);
+            split_equal_degree(div_rem(f, g).0, d, rng, out);
+            return;
+        }
+    }
+}
+
+fn irreducible_factors(f: u128, rng: &mut XorShift, out: &mut Vec<u128>) {
+    //every irreducible factor of f (with repeats, in no order). Squares and
+    //repeated factors are pulled out first, then distinct degree: the
+    //factors of degree i all divide x^(2^i) - x
+    if degree(f).unwrap_or(0) == 0 {
+        return;
+    }
+    let d = derivative(f);
+    if d == 0 {
+        let g = sqrt(f);
+        irreducible_factors(g, rng, out);
+        irreducible_factors(g, rng, out);
+        return;
+    }
+    let g = gcd(f, d);
+    if g != 1 {
+        irreducible_factors(g, rng, out);
+        irreducible_factors(div_rem(f, g).0, rng, out);
+        return;
+    }
+    let mut f = f;
+    let mut h = 2u128;
+    let mut i = 1;
+    while 2 * i <= degree(f).unwrap_or(0) {
+        h = mul_mod(h, h, f);
+        let g = gcd(f, h ^ 2);
+        if g != 1 {
+            split_equal_degree(g, i, rng, out);
+            f = div_rem(f, g).0;
+            h = div_rem(h, f).1;
+        }
+        i += 1;
+    }
+    if degree(f).unwrap_or(0) > 0 {
+        out.push(f);
+    }
+}
+
+pub fn factor(f: u128) -> Vec<(u128, u32)> {
+    //irreducible factors of f (degree 64 or less) with their powers, by
+    //degree then value
+    let mut all = Vec::new();
+    irreducible_factors(f, &mut XorShift(0x5eed_1234_abcd_ef01), &mut all);
+    all.sort_by_key(|&p| (degree(p), p));
+    let mut factors: Vec<(u128, u32)> = Vec::new();
+    for p in all {
+        match factors.last_mut() {
+            Some(last) if last.0 == p => last.1 += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+pub fn is_irreducible(f: u128) -> bool {
+    match factor(f).as_slice() {
+        [(_, 1)] => degree(f).unwrap_or(0) > 0,
+        _ => false,
+    }
+}
+
+fn mul_mod_u64(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+fn pow_mod_u64(a: u64, e: u64, n: u64) -> u64 {
+    let (mut a, mut e, mut r) = (a % n, e, 1 % n);
+    while e > 0 {
+        if e & 1 == 1 {
+            r = mul_mod_u64(r, a, n);
+        }
+        a = mul_mod_u64(a, a, n);
+        e >>= 1;
+    }
+    r
+}
+
+fn is_prime(n: u64) -> bool {
+    //miller rabin, these bases are enough for every u64
+    if n < 2 {
+        return false;
+    }
+    let bases = [2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if let Some(&p) = bases.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    bases.iter().all(|&a| {
+        let mut x = pow_mod_u64(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = mul_mod_u64(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+fn gcd_u64(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd_u64(b, a % b)
+    }
+}
+
+fn pollard_rho(n: u64) -> u64 {
+    //a non trivial factor of an odd composite n
+    (1u64..)
+        .filter_map(|c| {
+            let f = |x: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+            let (mut x, mut y, mut d) = (2u64, 2u64, 1u64);
+            while d == 1 {
+                x = f(x);
+                y = f(f(y));
+                d = gcd_u64(x.max(y) - x.min(y), n);
+            }
+            if d == n {
+                None
+            } else {
+                Some(d)
+            }
+        })
+        .next()
+        .unwrap()
+}
+
+fn prime_factors(n: u64) -> Vec<u64> {
+    //distinct prime factors, smallest first
+    let mut out = Vec::new();
+    let mut stack = vec![n];
+    while let Some(n) = stack.pop() {
+        if n < 2 {
+            continue;
+        }
+        if n.is_multiple_of(2) {
+            out.push(2);
+            stack.push(n >> n.trailing_zeros());
+        } else if is_prime(n) {
+            out.push(n);
+        } else {
+            let d = pollard_rho(n);
+            stack.push(d);
+            stack.push(n / d);
+        }
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn order_irreducible(p: u128) -> u128 {
+    //order of x mod an irreducible p of degree d divides 2^d - 1, so start
+    //there and take out prime factors while x^(n/q) is still 1
+    let d = degree(p).unwrap_or(0);
+    let full = if d == 64 { u64::MAX } else { (1u64 << d) - 1 };
+    let mut n = full;
+    for q in prime_factors(full) {
+        while n.is_multiple_of(q) && pow_mod(2, (n / q) as u128, p) == 1 {
+            n /= q;
+        }
+    }
+    n as u128
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        let r = x % y;
+        x = y;
+        y = r;
+    }
+    a / x * b
+}
+
+pub fn order(f: u128) -> Option<u128> {
+    //smallest e > 0 with x^e = 1 mod f, None if there isn't one (x divides f,
+    //or f is constant) or f is over degree 64. Made from the factors p^k of
+    //f: x has order ord(p) * 2^t mod p^k, for the smallest 2^t >= k
+    let d = degree(f)?;
+    if d == 0 || d > 64 || f & 1 == 0 {
+        return None;
+    }
+    Some(factor(f).iter().fold(1, |e, &(p, k)| {
+        let t = 32 - (k - 1).leading_zeros();
+        lcm(e, order_irreducible(p) << t)
+    }))
+}
+
+pub fn is_primitive(f: u128) -> bool {
+    //an LFSR with this connection polynomial goes through every non zero
+    //state, 2^m - 1 of them. Only possible when f is irreducible
+    match degree(f) {
+        Some(d) if d > 0 && d <= 64 => order(f) == Some((1u128 << d) - 1),
+        _ => false,
+    }
+}